@@ -8,18 +8,24 @@ use crate::structs::api::{ApiError, ApiErrorResult, ApiResponse};
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum AccountError {
     InvalidAddress,
+    InvalidPublicKey,
+    IndexOutOfRange,
 }
 
 impl AccountError {
     pub fn error(&self) -> &'static str {
         match self {
             AccountError::InvalidAddress => "INVALID_ADDRESS", // used
+            AccountError::InvalidPublicKey => "INVALID_PUBLIC_KEY",
+            AccountError::IndexOutOfRange => "INDEX_OUT_OF_RANGE",
         }
     }
 
     pub fn message(&self) -> String {
         match self {
             AccountError::InvalidAddress => "Address must start with 'nano_' or 'xrb_'".to_string(),
+            AccountError::InvalidPublicKey => "Public key must be 64 hex characters".to_string(),
+            AccountError::IndexOutOfRange => "Account index range must hold 1-100 indexes within u32".to_string(),
         }
     }
 
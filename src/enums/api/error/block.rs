@@ -18,6 +18,20 @@ pub enum BlockError {
     InvalidWorkRoot,
     InvalidWorkRootLength,
     CalculateWorkFailed,
+    UnopenedAccount,
+    RepresentativeUnchanged,
+    InvalidBlockType,
+    InvalidSubtype,
+    InvalidAccountAddress,
+    InvalidBalance,
+    MalformedSignature,
+    SubtypeMismatch,
+    BalanceMismatch,
+    SignatureMismatch,
+    InsufficientWork,
+    IncompleteBuildRequest,
+    MissingBlock,
+    InvalidBlockLength,
 }
 
 impl BlockError {
@@ -35,6 +49,20 @@ impl BlockError {
             BlockError::InvalidWorkRoot => "INVALID_WORK_ROOT",
             BlockError::InvalidWorkRootLength => "INVALID_WORK_ROOT_LENGTH",
             BlockError::CalculateWorkFailed => "CALCULATE_WORK_FAILED",
+            BlockError::UnopenedAccount => "ACCOUNT_NOT_OPENED",
+            BlockError::RepresentativeUnchanged => "REPRESENTATIVE_UNCHANGED",
+            BlockError::InvalidBlockType => "INVALID_BLOCK_TYPE",
+            BlockError::InvalidSubtype => "INVALID_SUBTYPE",
+            BlockError::InvalidAccountAddress => "INVALID_ACCOUNT_ADDRESS",
+            BlockError::InvalidBalance => "INVALID_BALANCE",
+            BlockError::MalformedSignature => "MALFORMED_SIGNATURE",
+            BlockError::SubtypeMismatch => "SUBTYPE_MISMATCH",
+            BlockError::BalanceMismatch => "BALANCE_MISMATCH",
+            BlockError::SignatureMismatch => "SIGNATURE_MISMATCH",
+            BlockError::InsufficientWork => "INSUFFICIENT_WORK",
+            BlockError::IncompleteBuildRequest => "INCOMPLETE_BUILD_REQUEST",
+            BlockError::MissingBlock => "MISSING_BLOCK",
+            BlockError::InvalidBlockLength => "INVALID_BLOCK_LENGTH",
         }
     }
 
@@ -52,12 +80,37 @@ impl BlockError {
             BlockError::InvalidWorkRoot => "Invalid work root".to_string(),
             BlockError::InvalidWorkRootLength => "Work root must be exactly 32 bytes".to_string(),
             BlockError::CalculateWorkFailed => "Failed to calculate work value".to_string(),
+            BlockError::UnopenedAccount => "Account has no blocks yet; receive funds before changing its representative".to_string(),
+            BlockError::RepresentativeUnchanged => "Account already uses this representative".to_string(),
+            BlockError::InvalidBlockType => "Only state blocks are supported".to_string(),
+            BlockError::InvalidSubtype => "Subtype must be one of send, receive, open or change".to_string(),
+            BlockError::InvalidAccountAddress => "Invalid block account address".to_string(),
+            BlockError::InvalidBalance => "Block balance must be an integer amount of raw".to_string(),
+            BlockError::MalformedSignature => "Block signature must be 128 hex characters".to_string(),
+            BlockError::SubtypeMismatch => "Block previous or link does not match its subtype".to_string(),
+            BlockError::BalanceMismatch => "Block balance change does not match its subtype and the previous balance".to_string(),
+            BlockError::SignatureMismatch => "Block signature does not verify against the account".to_string(),
+            BlockError::InsufficientWork => "Block work is below the threshold for its subtype".to_string(),
+            BlockError::MissingBlock => "Provide exactly one of block, binary or hash".to_string(),
+            BlockError::InvalidBlockLength => "Binary state blocks must be exactly 216 bytes".to_string(),
+            BlockError::IncompleteBuildRequest => "send needs destination and amount, receive needs source and amount, change needs representative".to_string(),
+        }
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self {
+            BlockError::UnopenedAccount | BlockError::RepresentativeUnchanged => StatusCode::CONFLICT,
+            BlockError::IncompleteBuildRequest | BlockError::MissingBlock => StatusCode::BAD_REQUEST,
+            BlockError::InvalidBlockLength | BlockError::InvalidBlockType | BlockError::InvalidSubtype | BlockError::InvalidAccountAddress | BlockError::InvalidBalance
+                | BlockError::MalformedSignature | BlockError::SubtypeMismatch | BlockError::BalanceMismatch | BlockError::SignatureMismatch
+                | BlockError::InsufficientWork => StatusCode::UNPROCESSABLE_ENTITY,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
 
     pub fn to_response(self) -> ApiErrorResult {
         (
-            StatusCode::INTERNAL_SERVER_ERROR,
+            self.status(),
             Json(ApiResponse { 
                 success: false, 
                 data: None, 
@@ -0,0 +1,51 @@
+use axum::Json;
+use reqwest::StatusCode;
+use serde::{Serialize, Deserialize};
+
+use crate::structs::api::{ApiError, ApiErrorResult, ApiResponse};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum MessageError {
+    EmptyMessage,
+    InvalidSignature,
+    SigningFailed,
+}
+
+impl MessageError {
+    pub fn error(&self) -> &'static str {
+        match self {
+            MessageError::EmptyMessage => "EMPTY_MESSAGE",
+            MessageError::InvalidSignature => "INVALID_SIGNATURE",
+            MessageError::SigningFailed => "SIGNING_FAILED",
+        }
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            MessageError::EmptyMessage => "Message must not be empty".to_string(),
+            MessageError::InvalidSignature => "Signature must be 128 hex characters".to_string(),
+            MessageError::SigningFailed => "Failed to sign message".to_string(),
+        }
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self {
+            MessageError::EmptyMessage | MessageError::InvalidSignature => StatusCode::BAD_REQUEST,
+            MessageError::SigningFailed => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    pub fn to_response(self) -> ApiErrorResult {
+        (
+            self.status(),
+            Json(ApiResponse { 
+                success: false, 
+                data: None, 
+                error: Some(ApiError {
+                    error: self.error().to_string(),
+                    message: self.message(),
+                }),
+            })
+        )
+    }
+}
@@ -3,4 +3,10 @@ pub mod api;
 pub mod transaction;
 pub mod account;
 pub mod block;
-pub mod work_server;
\ No newline at end of file
+pub mod work_server;
+pub mod wallet;
+pub mod message;
+pub mod uri;
+pub mod qr;
+pub mod node;
+pub mod publish;
\ No newline at end of file
@@ -0,0 +1,44 @@
+use axum::Json;
+use reqwest::StatusCode;
+use serde::{Serialize, Deserialize};
+
+use crate::structs::api::{ApiError, ApiErrorResult, ApiResponse};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum NodeRpcError {
+    Unreachable,
+    InvalidResponse,
+    Rejected(String),
+}
+
+impl NodeRpcError {
+    pub fn error(&self) -> &'static str {
+        match self {
+            NodeRpcError::Unreachable => "NODE_RPC_UNREACHABLE",
+            NodeRpcError::InvalidResponse => "NODE_RPC_INVALID_RESPONSE",
+            NodeRpcError::Rejected(_) => "NODE_RPC_REJECTED",
+        }
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            NodeRpcError::Unreachable => "Nano node RPC could not be reached".to_string(),
+            NodeRpcError::InvalidResponse => "Failed to parse Nano node RPC response".to_string(),
+            NodeRpcError::Rejected(details) => format!("Nano node RPC returned an error: {}", details),
+        }
+    }
+
+    pub fn to_response(self) -> ApiErrorResult {
+        (
+            StatusCode::BAD_GATEWAY,
+            Json(ApiResponse { 
+                success: false, 
+                data: None, 
+                error: Some(ApiError {
+                    error: self.error().to_string(),
+                    message: self.message(),
+                }),
+            })
+        )
+    }
+}
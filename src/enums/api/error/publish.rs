@@ -0,0 +1,62 @@
+use axum::Json;
+use reqwest::StatusCode;
+use serde::{Serialize, Deserialize};
+
+use crate::structs::api::{ApiError, ApiErrorResult, ApiResponse};
+
+/// Publishing failed for good. Each variant carries the attempt trail, e.g. `1. fork (...) -> rebuilt; 2. fork (...)`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum PublishError {
+    Fork(String),
+    GapPrevious(String),
+    InsufficientWork(String),
+    Unreachable(String),
+    WorkUnavailable(String),
+    Rejected(String),
+}
+
+impl PublishError {
+    pub fn error(&self) -> &'static str {
+        match self {
+            PublishError::Fork(_) => "PUBLISH_FORK",
+            PublishError::GapPrevious(_) => "PUBLISH_GAP_PREVIOUS",
+            PublishError::InsufficientWork(_) => "PUBLISH_INSUFFICIENT_WORK",
+            PublishError::Unreachable(_) => "PUBLISH_BACKEND_UNREACHABLE",
+            PublishError::WorkUnavailable(_) => "PUBLISH_WORK_UNAVAILABLE",
+            PublishError::Rejected(_) => "PUBLISH_REJECTED",
+        }
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            PublishError::Fork(trail) => format!("Block conflicts with another block on the same previous. Attempts: {}", trail),
+            PublishError::GapPrevious(trail) => format!("Ledger does not know the block's previous block. Attempts: {}", trail),
+            PublishError::InsufficientWork(trail) => format!("Ledger rejected the block's work as insufficient. Attempts: {}", trail),
+            PublishError::Unreachable(trail) => format!("No ledger backend could be reached. Attempts: {}", trail),
+            PublishError::WorkUnavailable(trail) => format!("Work for the repaired block could not be generated. Attempts: {}", trail),
+            PublishError::Rejected(trail) => format!("Ledger rejected the block. Attempts: {}", trail),
+        }
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self {
+            PublishError::Fork(_) | PublishError::GapPrevious(_) => StatusCode::CONFLICT,
+            PublishError::InsufficientWork(_) | PublishError::Rejected(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            PublishError::Unreachable(_) | PublishError::WorkUnavailable(_) => StatusCode::BAD_GATEWAY,
+        }
+    }
+
+    pub fn to_response(self) -> ApiErrorResult {
+        (
+            self.status(),
+            Json(ApiResponse {
+                success: false,
+                data: None,
+                error: Some(ApiError {
+                    error: self.error().to_string(),
+                    message: self.message(),
+                }),
+            })
+        )
+    }
+}
\ No newline at end of file
@@ -0,0 +1,55 @@
+use axum::Json;
+use reqwest::StatusCode;
+use serde::{Serialize, Deserialize};
+
+use crate::structs::api::{ApiError, ApiErrorResult, ApiResponse};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum QrError {
+    InvalidSize,
+    DataTooLong,
+    RenderFailed,
+    PaymentNotFound,
+}
+
+impl QrError {
+    pub fn error(&self) -> &'static str {
+        match self {
+            QrError::InvalidSize => "INVALID_QR_SIZE",
+            QrError::DataTooLong => "QR_DATA_TOO_LONG",
+            QrError::RenderFailed => "QR_RENDER_FAILED",
+            QrError::PaymentNotFound => "PAYMENT_NOT_FOUND",
+        }
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            QrError::InvalidSize => "QR size must be between 64 and 2048 pixels".to_string(),
+            QrError::DataTooLong => "Data is too long to fit in a QR code".to_string(),
+            QrError::RenderFailed => "Failed to render QR code".to_string(),
+            QrError::PaymentNotFound => "No receive address and amount known for this payment request".to_string(),
+        }
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self {
+            QrError::InvalidSize | QrError::DataTooLong => StatusCode::BAD_REQUEST,
+            QrError::RenderFailed => StatusCode::INTERNAL_SERVER_ERROR,
+            QrError::PaymentNotFound => StatusCode::NOT_FOUND,
+        }
+    }
+
+    pub fn to_response(self) -> ApiErrorResult {
+        (
+            self.status(),
+            Json(ApiResponse { 
+                success: false, 
+                data: None, 
+                error: Some(ApiError {
+                    error: self.error().to_string(),
+                    message: self.message(),
+                }),
+            })
+        )
+    }
+}
@@ -0,0 +1,47 @@
+use axum::Json;
+use reqwest::StatusCode;
+use serde::{Serialize, Deserialize};
+
+use crate::structs::api::{ApiError, ApiErrorResult, ApiResponse};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum UriError {
+    InvalidScheme,
+    InvalidAmount,
+    InvalidEncoding,
+    MissingAmount,
+}
+
+impl UriError {
+    pub fn error(&self) -> &'static str {
+        match self {
+            UriError::InvalidScheme => "INVALID_URI_SCHEME",
+            UriError::InvalidAmount => "INVALID_URI_AMOUNT",
+            UriError::InvalidEncoding => "INVALID_URI_ENCODING",
+            UriError::MissingAmount => "MISSING_AMOUNT",
+        }
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            UriError::InvalidScheme => "URI must start with 'nano:' followed by an address".to_string(),
+            UriError::InvalidAmount => "URI amount must be an integer amount of raw".to_string(),
+            UriError::InvalidEncoding => "URI contains invalid percent-encoding".to_string(),
+            UriError::MissingAmount => "The URI has no amount; provide one in the request".to_string(),
+        }
+    }
+
+    pub fn to_response(self) -> ApiErrorResult {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse { 
+                success: false, 
+                data: None, 
+                error: Some(ApiError {
+                    error: self.error().to_string(),
+                    message: self.message(),
+                }),
+            })
+        )
+    }
+}
@@ -0,0 +1,145 @@
+use axum::Json;
+use reqwest::StatusCode;
+use serde::{Serialize, Deserialize};
+
+use crate::structs::api::{ApiError, ApiErrorResult, ApiResponse};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum WalletError {
+    AccountNotFound,
+    AccountDerivationFailed,
+    WalletSaveFailed,
+    WalletNotFound,
+    WalletAlreadyExists,
+    InvalidWalletName,
+    WalletInUse,
+    WalletDecryptionFailed,
+    WalletRegistrationFailed,
+    RegistrySaveFailed,
+    RegistryCorrupted,
+    InvalidImportSource,
+    InvalidSeed,
+    InvalidPrivateKey,
+    InvalidMnemonic,
+    WrongPassword,
+    InvalidPassword,
+    BackupFailed,
+    EnvUpdateFailed,
+    WalletLocked,
+    InvalidBackup,
+    SeedNotAvailable,
+    InvalidShareThreshold,
+    InvalidShares,
+    ShareVerificationFailed,
+    WatchOnlyAccount,
+    WatchAddressExists,
+}
+
+impl WalletError {
+    pub fn error(&self) -> &'static str {
+        match self {
+            WalletError::AccountNotFound => "ACCOUNT_NOT_FOUND",
+            WalletError::AccountDerivationFailed => "ACCOUNT_DERIVATION_FAILED",
+            WalletError::WalletSaveFailed => "WALLET_SAVE_FAILED",
+            WalletError::WalletNotFound => "WALLET_NOT_FOUND",
+            WalletError::WalletAlreadyExists => "WALLET_ALREADY_EXISTS",
+            WalletError::InvalidWalletName => "INVALID_WALLET_NAME",
+            WalletError::WalletInUse => "WALLET_IN_USE",
+            WalletError::WalletDecryptionFailed => "WALLET_DECRYPTION_FAILED",
+            WalletError::WalletRegistrationFailed => "WALLET_REGISTRATION_FAILED",
+            WalletError::RegistrySaveFailed => "REGISTRY_SAVE_FAILED",
+            WalletError::RegistryCorrupted => "REGISTRY_CORRUPTED",
+            WalletError::InvalidImportSource => "INVALID_IMPORT_SOURCE",
+            WalletError::InvalidSeed => "INVALID_SEED",
+            WalletError::InvalidPrivateKey => "INVALID_PRIVATE_KEY",
+            WalletError::InvalidMnemonic => "INVALID_MNEMONIC",
+            WalletError::WrongPassword => "WRONG_PASSWORD",
+            WalletError::InvalidPassword => "INVALID_PASSWORD",
+            WalletError::BackupFailed => "BACKUP_FAILED",
+            WalletError::EnvUpdateFailed => "ENV_UPDATE_FAILED",
+            WalletError::WalletLocked => "WALLET_LOCKED",
+            WalletError::InvalidBackup => "INVALID_BACKUP",
+            WalletError::SeedNotAvailable => "SEED_NOT_AVAILABLE",
+            WalletError::InvalidShareThreshold => "INVALID_SHARE_THRESHOLD",
+            WalletError::InvalidShares => "INVALID_SHARES",
+            WalletError::ShareVerificationFailed => "SHARE_VERIFICATION_FAILED",
+            WalletError::WatchOnlyAccount => "WATCH_ONLY_ACCOUNT",
+            WalletError::WatchAddressExists => "WATCH_ADDRESS_EXISTS",
+        }
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            WalletError::AccountNotFound => "No wallet account matches the provided index, address or label".to_string(),
+            WalletError::AccountDerivationFailed => "Failed to derive wallet account from seed".to_string(),
+            WalletError::WalletSaveFailed => "Failed to save wallet file".to_string(),
+            WalletError::WalletNotFound => "No wallet with the provided name is registered".to_string(),
+            WalletError::WalletAlreadyExists => "A wallet with the provided name already exists".to_string(),
+            WalletError::InvalidWalletName => "Wallet name must be 1-64 characters of letters, digits, '-' or '_'".to_string(),
+            WalletError::WalletInUse => "The selected wallet cannot be deleted; select another wallet first".to_string(),
+            WalletError::WalletDecryptionFailed => "Failed to decrypt wallet file (wrong password or corrupted data)".to_string(),
+            WalletError::WalletRegistrationFailed => "Failed to register wallet address with the API".to_string(),
+            WalletError::RegistrySaveFailed => "Failed to save wallet registry".to_string(),
+            WalletError::RegistryCorrupted => "Wallet registry file cannot be parsed; restore data/wallets/registry.json".to_string(),
+            WalletError::InvalidImportSource => "Provide exactly one of seed, private_key or mnemonic (private keys import a single account)".to_string(),
+            WalletError::InvalidSeed => "Seed must be 64 hex characters".to_string(),
+            WalletError::InvalidPrivateKey => "Private key must be 64 hex characters".to_string(),
+            WalletError::InvalidMnemonic => "Mnemonic is not a valid English BIP39 phrase".to_string(),
+            WalletError::WrongPassword => "The provided wallet password is incorrect".to_string(),
+            WalletError::InvalidPassword => "New password must be at least 12 characters and contain no quotes or line breaks".to_string(),
+            WalletError::BackupFailed => "Failed to back up wallet file".to_string(),
+            WalletError::EnvUpdateFailed => "Failed to update AI_WALLET_PASSWORD in .env; the wallets keep the current password".to_string(),
+            WalletError::WalletLocked => "The wallet is locked; unlock it with the wallet password first".to_string(),
+            WalletError::InvalidBackup => "Backup is not a hex-encoded wallet file or the backup password is wrong".to_string(),
+            WalletError::SeedNotAvailable => "Private key wallets have no seed to split or recover".to_string(),
+            WalletError::InvalidShareThreshold => "Threshold must be at least 2 and at most the share count (max 255)".to_string(),
+            WalletError::InvalidShares => "Shares are malformed, inconsistent or fewer than the threshold".to_string(),
+            WalletError::ShareVerificationFailed => "The recovered seed does not derive the expected address".to_string(),
+            WalletError::WatchOnlyAccount => "Watch-only accounts cannot sign or spend".to_string(),
+            WalletError::WatchAddressExists => "The address is already an account or watch-only entry of this wallet".to_string(),
+        }
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self {
+            WalletError::AccountNotFound | WalletError::WalletNotFound => StatusCode::NOT_FOUND,
+            WalletError::InvalidWalletName
+            | WalletError::InvalidImportSource
+            | WalletError::InvalidSeed
+            | WalletError::InvalidPrivateKey
+            | WalletError::InvalidMnemonic
+            | WalletError::InvalidPassword
+            | WalletError::InvalidBackup
+            | WalletError::SeedNotAvailable
+            | WalletError::InvalidShareThreshold
+            | WalletError::InvalidShares
+            | WalletError::ShareVerificationFailed => StatusCode::BAD_REQUEST,
+            WalletError::WrongPassword => StatusCode::UNAUTHORIZED,
+            WalletError::WatchOnlyAccount => StatusCode::FORBIDDEN,
+            WalletError::WalletLocked => StatusCode::LOCKED,
+            WalletError::WalletAlreadyExists | WalletError::WalletInUse | WalletError::WatchAddressExists => StatusCode::CONFLICT,
+            WalletError::WalletRegistrationFailed => StatusCode::BAD_GATEWAY,
+            WalletError::AccountDerivationFailed
+            | WalletError::WalletSaveFailed
+            | WalletError::WalletDecryptionFailed
+            | WalletError::RegistrySaveFailed
+            | WalletError::RegistryCorrupted
+            | WalletError::BackupFailed
+            | WalletError::EnvUpdateFailed => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    pub fn to_response(self) -> ApiErrorResult {
+        (
+            self.status(),
+            Json(ApiResponse { 
+                success: false, 
+                data: None, 
+                error: Some(ApiError {
+                    error: self.error().to_string(),
+                    message: self.message(),
+                }),
+            })
+        )
+    }
+}
@@ -7,18 +7,21 @@ use crate::structs::api::{ApiError, ApiErrorResult, ApiResponse};
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum WorkServerError {
     Error(String),
+    Unreachable(String),
 }
 
 impl WorkServerError {
     pub fn error(&self) -> &'static str {
         match self {
             WorkServerError::Error(_) => "WORK_SERVER_ERROR",
+            WorkServerError::Unreachable(_) => "WORK_SERVER_UNREACHABLE",
         }
     }
 
     pub fn message(&self) -> String {
         match self {
             WorkServerError::Error(msg) => format!("Work server error: {}", msg),
+            WorkServerError::Unreachable(details) => format!("Work server could not be reached: {}", details),
         }
     }
 
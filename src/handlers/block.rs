@@ -0,0 +1,116 @@
+use axum::Json;
+
+use crate::{enums::api::error::block::BlockError, helpers::{api::api_success, block::decode_state_block, nano::{hex_to_bytes, nano_address_to_public_key, nano_to_raw, public_key_to_nano_address}}, 
+    services::{ledger::pool::ledger, nano::{block::{attach_work, complete_signed_block, offline_block, sign_block_offline, unopened_account_state}, 
+    inspect::{inspect_block, inspect_legacy_block}, queue::run_queued, recovery::{BlockSource, publish_with_recovery}, validation::ZERO_HASH}, wallet::wallet::{get_wallet_data, resolve_wallet_account}}, structs::{api::{ApiErrorResult, ApiSuccessResult}, 
+    block::{BlockActionApi, BlockInfoResponseApi, LedgerBlock, BlockInspectionApi, BlockIntent, BuildBlockRequestApi, InspectBlockRequestApi, OfflineBlockApi, PublishSignedBlockRequestApi, 
+    PublishedBlockApi}}};
+
+/// Builds and signs a block from caller-supplied account state, without contacting the ledger or the work server.
+pub async fn build_block_api(Json(payload): Json<BuildBlockRequestApi>) -> Result<ApiSuccessResult<OfflineBlockApi>, ApiErrorResult> {
+    let wallet_data = get_wallet_data(payload.wallet.as_deref())?;
+    let account = resolve_wallet_account(&wallet_data, payload.account.as_deref())?;
+    let state = payload.state.clone().unwrap_or_else(|| unopened_account_state(&account));
+
+    let offline = match payload.action {
+        BlockActionApi::Send => {
+            let destination = payload.destination.as_deref().ok_or_else(|| BlockError::IncompleteBuildRequest.to_response())?;
+            let link = nano_address_to_public_key(destination, true)?;
+            let amount_raw = requested_amount_raw(&payload)?;
+            sign_block_offline(&wallet_data, &account, &state, BlockIntent::Send { amount_raw, link: &link })?
+        }
+        BlockActionApi::Receive => {
+            let source = payload.source.as_deref().map(str::trim).ok_or_else(|| BlockError::IncompleteBuildRequest.to_response())?;
+            if hex_to_bytes(source).map(|bytes| bytes.len() != 32).unwrap_or(true) {
+                return Err(BlockError::InvalidLink.to_response());
+            }
+            let amount_raw = requested_amount_raw(&payload)?;
+            sign_block_offline(&wallet_data, &account, &state, BlockIntent::Receive { amount_raw, link: &source.to_uppercase() })?
+        }
+        BlockActionApi::Change => {
+            let representative = payload.representative.as_deref().ok_or_else(|| BlockError::IncompleteBuildRequest.to_response())?;
+            let representative_public_key = nano_address_to_public_key(representative, true)?;
+            sign_block_offline(&wallet_data, &account, &state, BlockIntent::Change { representative_public_key: &representative_public_key })?
+        }
+    };
+
+    match payload.work.as_deref().map(str::trim).filter(|work| !work.is_empty()) {
+        Some(work) => {
+            let signed_block = attach_work(offline.clone(), work)?;
+            Ok(api_success(OfflineBlockApi { block: signed_block.block, ..offline }))
+        }
+        None => Ok(api_success(offline)),
+    }
+}
+
+/// Publishes a pre-signed block, attaching work from the request or the work server if it has none.
+/// Insufficient work is regenerated and unreachable backends retried; a fork cannot be repaired without re-signing.
+pub async fn publish_signed_block_api(Json(payload): Json<PublishSignedBlockRequestApi>) -> Result<ApiSuccessResult<PublishedBlockApi>, ApiErrorResult> {
+    let wallet_data = get_wallet_data(payload.wallet.as_deref())?;
+    let previous_balance = payload.previous_balance.trim().parse::<u128>()
+        .map_err(|_| BlockError::InvalidBalance.to_response())?;
+    let work = payload.work
+        .or_else(|| Some(payload.block.work.clone()))
+        .filter(|work| !work.trim().is_empty());
+
+    let offline = offline_block(payload.block, payload.subtype.trim(), previous_balance)?;
+    let signed_block = complete_signed_block(offline, work).await?;
+    // Queued under the canonical address, so `xrb_` or differently cased input waits behind the wallet's own operations.
+    let account = public_key_to_nano_address(&signed_block.account_public_key)
+        .map_err(|_| BlockError::InvalidAccountAddress.to_response())?;
+    let (result, queue_position) = run_queued(&account, "publish", || {
+        publish_with_recovery(ledger(), signed_block, BlockSource::Presigned { previous_balance }, &wallet_data.api_key)
+    }).await;
+    let (signed_block, attempts) = result?;
+
+    Ok(api_success(PublishedBlockApi {
+        account,
+        subtype: signed_block.subtype,
+        hash: signed_block.hash,
+        queue_position,
+        attempts,
+    }))
+}
+
+/// Decodes a block given as JSON or fetched by hash. The previous balance is looked up when not supplied.
+pub async fn inspect_block_api(Json(payload): Json<InspectBlockRequestApi>) -> Result<ApiSuccessResult<BlockInspectionApi>, ApiErrorResult> {
+    let block = match (payload.block, payload.binary.as_deref(), payload.hash.as_deref().map(str::trim)) {
+        (Some(block), None, None) => block,
+        (None, Some(binary), None) => decode_state_block(&hex_to_bytes(binary).map_err(|_| BlockError::InvalidBlockLength.to_response())?)?,
+        (None, None, Some(hash)) => {
+            let info = fetch_block(hash, payload.wallet.as_deref()).await?;
+            match info.contents {
+                LedgerBlock::State(block) => block,
+                LedgerBlock::Legacy(block) => {
+                    let balance = info.balance.trim().parse::<u128>().map_err(|_| BlockError::InvalidBalance.to_response())?;
+                    let amount_raw = info.amount.trim().parse::<u128>().ok();
+                    return Ok(api_success(inspect_legacy_block(&block, &info.block_account, balance, amount_raw)?));
+                }
+            }
+        }
+        _ => return Err(BlockError::MissingBlock.to_response()),
+    };
+
+    let previous_balance = match payload.previous_balance.as_deref() {
+        Some(previous_balance) => Some(previous_balance.trim().parse::<u128>().map_err(|_| BlockError::InvalidBalance.to_response())?),
+        None if block.previous.trim() == ZERO_HASH => Some(0),
+        None => fetch_block(&block.previous, payload.wallet.as_deref()).await.ok()
+            .and_then(|previous| previous.contents.balance().or_else(|| previous.balance.trim().parse::<u128>().ok())),
+    };
+
+    Ok(api_success(inspect_block(&block, previous_balance)?))
+}
+
+async fn fetch_block(hash: &str, wallet: Option<&str>) -> Result<BlockInfoResponseApi, ApiErrorResult> {
+    let wallet_data = get_wallet_data(wallet)?;
+
+    ledger().block_info(hash, &wallet_data.api_key).await
+}
+
+fn requested_amount_raw(payload: &BuildBlockRequestApi) -> Result<u128, ApiErrorResult> {
+    match (payload.amount.as_deref(), payload.amount_raw.as_deref()) {
+        (Some(amount), None) => nano_to_raw(amount),
+        (None, Some(amount_raw)) => amount_raw.trim().parse::<u128>().map_err(|_| BlockError::InvalidBalance.to_response()),
+        _ => Err(BlockError::IncompleteBuildRequest.to_response()),
+    }
+}
@@ -1,17 +1,20 @@
-use axum::{Json, extract::Path};
+use axum::{Json, extract::{Path, Query}};
 
-use crate::{enums::api::error::api::ApiErrorType, handlers::wallet::send_nano_api, helpers::api::api_success, services::{ifenpay::api::ifenpay_api_get, wallet::wallet::get_wallet_data}, structs::{api::{ApiErrorResult, ApiSuccessResult}, donate::{DonateAddressResponse, DonateResponseApi}, wallet::SendNanoRequestApi}};
+use crate::{enums::api::error::api::ApiErrorType, handlers::wallet::send_nano_api, helpers::api::api_success, services::{ifenpay::api::ifenpay_api_get, wallet::wallet::get_wallet_data}, structs::{api::{ApiErrorResult, ApiSuccessResult}, donate::{DonateAddressResponse, DonateResponseApi}, wallet::{SendNanoRequestApi, WalletSelectorApi}}};
 
 pub async fn donate_api(
-    Path(amount): Path<String>
+    Path(amount): Path<String>,
+    Query(selector): Query<WalletSelectorApi>,
 ) -> Result<ApiSuccessResult<DonateResponseApi>, ApiErrorResult> {
-    let wallet_data = get_wallet_data();
+    let wallet_data = get_wallet_data(selector.wallet.as_deref())?;
 
     let donate_address = ifenpay_api_get::<DonateAddressResponse>("/donate/address", Some(&wallet_data.api_key)).await?.data.ok_or_else(|| ApiErrorType::ParseError.to_response())?;
 
     let result = send_nano_api(Json(SendNanoRequestApi {
         recipient_address: donate_address.address.clone(),
         amount: amount.clone(),
+        wallet: selector.wallet,
+        account: selector.account,
     })).await;
 
     Ok(api_success(DonateResponseApi {
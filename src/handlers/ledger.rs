@@ -0,0 +1,6 @@
+use crate::{helpers::api::api_success, services::ledger::pool::ledger, structs::{api::{ApiErrorResult, ApiSuccessResult}, ledger::LedgerStatusResponseApi}};
+
+/// Health of each configured ledger backend and which backend served the most recent ledger requests.
+pub async fn get_ledger_status_api() -> Result<ApiSuccessResult<LedgerStatusResponseApi>, ApiErrorResult> {
+    Ok(api_success(ledger().status()))
+}
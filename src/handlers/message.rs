@@ -0,0 +1,45 @@
+use axum::Json;
+
+use crate::{enums::api::error::message::MessageError, helpers::{api::api_success, nano::{hex_to_bytes, nano_address_to_public_key, nano_message_hash, 
+    sign_hash_with_private_key, verify_hash_signature}}, services::wallet::wallet::{get_wallet_data, resolve_wallet_account, wallet_account_private_key}, 
+    structs::{api::{ApiErrorResult, ApiSuccessResult}, message::{SignMessageRequestApi, SignMessageResponseApi, VerifyMessageRequestApi, VerifyMessageResponseApi}}};
+
+pub async fn sign_message_api(Json(payload): Json<SignMessageRequestApi>) -> Result<ApiSuccessResult<SignMessageResponseApi>, ApiErrorResult> {
+    if payload.message.is_empty() {
+        return Err(MessageError::EmptyMessage.to_response());
+    }
+
+    let wallet_data = get_wallet_data(payload.wallet.as_deref())?;
+    let account = resolve_wallet_account(&wallet_data, payload.account.as_deref())?;
+
+    let account_pub32 = hex_to_bytes(&account.public_key).map_err(|_| MessageError::SigningFailed.to_response())?;
+    let hash = nano_message_hash(&account_pub32, &payload.message).map_err(|_| MessageError::SigningFailed.to_response())?;
+    let private_key = wallet_account_private_key(&wallet_data, account.index).map_err(|_| MessageError::SigningFailed.to_response())?;
+    let signature = sign_hash_with_private_key(&private_key, &hash).map_err(|_| MessageError::SigningFailed.to_response())?;
+
+    Ok(api_success(SignMessageResponseApi {
+        account: account.address,
+        message: payload.message,
+        hash: hex::encode_upper(hash),
+        signature: signature.to_uppercase(),
+    }))
+}
+
+pub async fn verify_message_api(Json(payload): Json<VerifyMessageRequestApi>) -> Result<ApiSuccessResult<VerifyMessageResponseApi>, ApiErrorResult> {
+    let public_key = nano_address_to_public_key(&payload.address, true)?;
+    let account_pub32 = hex_to_bytes(&public_key).map_err(|_| MessageError::InvalidSignature.to_response())?;
+    let signature = hex_to_bytes(&payload.signature)
+        .ok()
+        .filter(|signature| signature.len() == 64)
+        .ok_or_else(|| MessageError::InvalidSignature.to_response())?;
+
+    // An address that is not a valid curve point cannot have signed anything.
+    let valid = nano_message_hash(&account_pub32, &payload.message)
+        .and_then(|hash| verify_hash_signature(&account_pub32, &hash, &signature))
+        .unwrap_or(false);
+
+    Ok(api_success(VerifyMessageResponseApi {
+        account: payload.address.trim().to_string(),
+        valid,
+    }))
+}
@@ -1,4 +1,10 @@
 pub mod credits;
 pub mod payment;
 pub mod wallet;
-pub mod donate;
\ No newline at end of file
+pub mod wallets;
+pub mod watch;
+pub mod message;
+pub mod util;
+pub mod donate;
+pub mod block;
+pub mod ledger;
\ No newline at end of file
@@ -1,26 +1,91 @@
-use axum::{Json, extract::Path};
+use std::{collections::VecDeque, sync::Mutex};
+
+use axum::{Json, extract::{Path, Query}, response::Response};
+use once_cell::sync::Lazy;
 use serde_json::Value;
 
 use crate::{
-    enums::api::error::api::ApiErrorType,
-    helpers::api::api_success,
+    enums::api::error::{api::ApiErrorType, qr::QrError},
+    helpers::{api::api_success, nano::{nano_to_raw, validate_nano_address}, qr::qr_response, uri::build_nano_uri},
     services::{ifenpay::api::{ifenpay_api_get, ifenpay_api_post}, wallet::wallet::get_wallet_data},
-    structs::{api::{ApiErrorResult, ApiResponse, ApiSuccessResult}, payment::{CreatePaymentRequestApi, CreatePaymentResponseApi, StatusPaymentnApi}},
+    structs::{api::{ApiErrorResult, ApiResponse, ApiSuccessResult}, payment::{CreatePaymentRequestApi, CreatePaymentResponseApi, StatusPaymentnApi}, 
+        qr::PaymentQrQueryApi, wallet::WalletSelectorApi},
 };
 
+const PAYMENT_URI_CACHE_SIZE: usize = 500;
+
+/// Payment URIs of requests created by this process, so their QR codes need no upstream lookup.
+static PAYMENT_URIS: Lazy<Mutex<VecDeque<(String, String)>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
+
 pub async fn create_payment_request_api(
     Json(payload): Json<CreatePaymentRequestApi>
 ) -> Result<ApiSuccessResult<CreatePaymentResponseApi>, ApiErrorResult> {
-    let wallet_data = get_wallet_data();
-    let response = ifenpay_api_post("/payment/request", &payload, Some(&wallet_data.api_key)).await?.data.ok_or_else(|| ApiErrorType::ParseError.to_response())?;
+    let wallet_data = get_wallet_data(payload.wallet.as_deref())?;
+    let mut response: CreatePaymentResponseApi = ifenpay_api_post("/payment/request", &payload, Some(&wallet_data.api_key)).await?.data.ok_or_else(|| ApiErrorType::ParseError.to_response())?;
+    response.payment_uri = payment_uri(&response.receive_address, &response.amount, &response.transaction_id);
+
+    if let Some(uri) = &response.payment_uri {
+        cache_payment_uri(&response.transaction_id, uri);
+    }
 
     Ok(api_success(response))
 }
 
+pub async fn get_payment_qr_api(
+    Path(transaction_id): Path<String>,
+    Query(query): Query<PaymentQrQueryApi>,
+) -> Result<Response, ApiErrorResult> {
+    let uri = match cached_payment_uri(&transaction_id) {
+        Some(uri) => uri,
+        None => {
+            let wallet_data = get_wallet_data(query.wallet.as_deref())?;
+            let response = ifenpay_api_get::<Value>(&format!("/payment/status/{}", transaction_id), Some(&wallet_data.api_key)).await?.data
+                .ok_or_else(|| ApiErrorType::ParseError.to_response())?;
+            payment_uri_from_status(&response, &transaction_id).ok_or_else(|| QrError::PaymentNotFound.to_response())?
+        }
+    };
+
+    qr_response(&uri, query.format.unwrap_or_default(), query.size)
+}
+
+fn payment_uri(receive_address: &str, amount: &str, transaction_id: &str) -> Option<String> {
+    let (address, _) = validate_nano_address(receive_address).ok()?;
+    let amount_raw = nano_to_raw(amount).ok()?;
+
+    Some(build_nano_uri(&address, Some(amount_raw), None, Some(transaction_id)))
+}
+
+fn cache_payment_uri(transaction_id: &str, uri: &str) {
+    let mut cache = PAYMENT_URIS.lock().unwrap();
+    cache.retain(|(id, _)| id != transaction_id);
+    if cache.len() >= PAYMENT_URI_CACHE_SIZE {
+        cache.pop_front();
+    }
+    cache.push_back((transaction_id.to_string(), uri.to_string()));
+}
+
+fn cached_payment_uri(transaction_id: &str) -> Option<String> {
+    let cache = PAYMENT_URIS.lock().unwrap();
+    cache.iter().find(|(id, _)| id == transaction_id).map(|(_, uri)| uri.clone())
+}
+
+/// Falls back to the upstream status payload, which may carry the receive address and amount.
+fn payment_uri_from_status(raw: &Value, transaction_id: &str) -> Option<String> {
+    let object = raw.get("data").filter(|data| data.is_object()).unwrap_or(raw).as_object()?;
+    let receive_address = object
+        .get("receive_address")
+        .or_else(|| object.get("receiveAddress"))
+        .and_then(Value::as_str)?;
+    let amount = object.get("amount").and_then(Value::as_str)?;
+
+    payment_uri(receive_address, amount, transaction_id)
+}
+
 pub async fn get_payment_status_api(
-    Path(transaction_id): Path<String>
+    Path(transaction_id): Path<String>,
+    Query(selector): Query<WalletSelectorApi>,
 ) -> Result<ApiSuccessResult<StatusPaymentnApi>, ApiErrorResult> {
-    let wallet_data = get_wallet_data();
+    let wallet_data = get_wallet_data(selector.wallet.as_deref())?;
     let response = ifenpay_api_get::<Value>(&format!("/payment/status/{}", transaction_id), Some(&wallet_data.api_key)).await?.data.ok_or_else(|| ApiErrorType::ParseError.to_response())?;
     let normalized = normalize_payment_status(response, &transaction_id).ok_or_else(|| ApiErrorType::ParseError.to_response())?;
 
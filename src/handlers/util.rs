@@ -0,0 +1,93 @@
+use axum::{Json, extract::Query};
+
+use crate::{enums::api::error::{account::AccountError, uri::UriError, wallet::WalletError}, helpers::{api::api_success, nano::{hex_to_bytes, nano_account_public_key, 
+    nano_to_raw, public_key_to_nano_address, validate_nano_address}, uri::{build_nano_uri, parse_nano_uri}}, structs::{api::{ApiErrorResult, ApiSuccessResult}, 
+    uri::{BuildUriRequestApi, BuildUriResponseApi, NanoUriApi, ParseUriRequestApi}, util::{AddressPublicKeyApi, AddressQueryApi, DeriveAddressesRequestApi, 
+    DeriveAddressesResponseApi, DerivedAddressApi, PublicKeyQueryApi, ValidateAddressResponseApi}}};
+
+const MAX_DERIVE_COUNT: u32 = 100;
+
+/// Always succeeds; an invalid address is reported through `valid`, `reason` and `message`.
+pub async fn validate_address_api(Query(query): Query<AddressQueryApi>) -> Result<ApiSuccessResult<ValidateAddressResponseApi>, ApiErrorResult> {
+    let response = match validate_nano_address(&query.address) {
+        Ok((normalized, public_key)) => ValidateAddressResponseApi {
+            address: query.address,
+            valid: true,
+            normalized: Some(normalized),
+            public_key: Some(public_key),
+            reason: None,
+            message: None,
+        },
+        Err(reason) => ValidateAddressResponseApi {
+            address: query.address,
+            valid: false,
+            normalized: None,
+            public_key: None,
+            reason: Some(reason),
+            message: Some(reason.message().to_string()),
+        },
+    };
+
+    Ok(api_success(response))
+}
+
+pub async fn address_to_public_key_api(Query(query): Query<AddressQueryApi>) -> Result<ApiSuccessResult<AddressPublicKeyApi>, ApiErrorResult> {
+    let (address, public_key) = validate_nano_address(&query.address).map_err(|_| AccountError::InvalidAddress.to_response())?;
+
+    Ok(api_success(AddressPublicKeyApi { address, public_key }))
+}
+
+pub async fn public_key_to_address_api(Query(query): Query<PublicKeyQueryApi>) -> Result<ApiSuccessResult<AddressPublicKeyApi>, ApiErrorResult> {
+    let public_key = hex_to_bytes(&query.public_key)
+        .ok()
+        .filter(|public_key| public_key.len() == 32)
+        .map(hex::encode_upper)
+        .ok_or_else(|| AccountError::InvalidPublicKey.to_response())?;
+    let address = public_key_to_nano_address(&public_key).map_err(|_| AccountError::InvalidPublicKey.to_response())?;
+
+    Ok(api_success(AddressPublicKeyApi { address, public_key }))
+}
+
+pub async fn derive_addresses_api(Json(payload): Json<DeriveAddressesRequestApi>) -> Result<ApiSuccessResult<DeriveAddressesResponseApi>, ApiErrorResult> {
+    if !hex_to_bytes(&payload.seed).is_ok_and(|seed| seed.len() == 32) {
+        return Err(WalletError::InvalidSeed.to_response());
+    }
+
+    let start = payload.start.unwrap_or(0);
+    let count = payload.count.unwrap_or(1);
+    if !(1..=MAX_DERIVE_COUNT).contains(&count) || start.checked_add(count - 1).is_none() {
+        return Err(AccountError::IndexOutOfRange.to_response());
+    }
+
+    let mut addresses = Vec::with_capacity(count as usize);
+    for index in start..=start + (count - 1) {
+        let public_key = nano_account_public_key(&payload.seed, index)
+            .map(hex::encode_upper)
+            .map_err(|_| WalletError::InvalidSeed.to_response())?;
+        let address = public_key_to_nano_address(&public_key).map_err(|_| WalletError::AccountDerivationFailed.to_response())?;
+
+        addresses.push(DerivedAddressApi { index, address, public_key });
+    }
+
+    Ok(api_success(DeriveAddressesResponseApi { addresses }))
+}
+
+pub async fn parse_uri_api(Query(query): Query<ParseUriRequestApi>) -> Result<ApiSuccessResult<NanoUriApi>, ApiErrorResult> {
+    let uri = parse_nano_uri(&query.uri)?;
+
+    Ok(api_success(uri))
+}
+
+pub async fn build_uri_api(Json(payload): Json<BuildUriRequestApi>) -> Result<ApiSuccessResult<BuildUriResponseApi>, ApiErrorResult> {
+    let (address, _) = validate_nano_address(&payload.address).map_err(|_| AccountError::InvalidAddress.to_response())?;
+    let amount_raw = match (payload.amount.as_deref(), payload.amount_raw.as_deref()) {
+        (Some(amount), None) => Some(nano_to_raw(amount)?),
+        (None, Some(amount_raw)) => Some(amount_raw.trim().parse::<u128>().map_err(|_| UriError::InvalidAmount.to_response())?),
+        (None, None) => None,
+        (Some(_), Some(_)) => return Err(UriError::InvalidAmount.to_response()),
+    };
+
+    Ok(api_success(BuildUriResponseApi {
+        uri: build_nano_uri(&address, amount_raw, payload.label.as_deref(), payload.message.as_deref()),
+    }))
+}
@@ -1,83 +1,158 @@
 use std::thread::sleep;
 
-use axum::{Json};
+use axum::{Json, extract::Query, response::Response};
 
-use crate::{enums::api::error::{account::AccountError, api::ApiErrorType}, helpers::{api::api_success, nano::{nano_address_to_public_key, nano_to_raw}}, 
-services::{ifenpay::api::{ifenpay_api_get, ifenpay_api_post}, nano::block::prepare_generate_and_sign_block, wallet::wallet::get_wallet_data}, structs::{api::{ApiErrorResult, ApiSuccessResult}, 
-block::{BlockInfoResponseApi, PublishBlockRequestApi, PublishBlockResponseApi}, wallet::{BalanceResponseApi, BlocksPerAccountResponse, SendNanoRequestApi, SendNanoResponseApi, 
-    WalletData}}};
+use crate::{enums::api::error::{account::AccountError, api::ApiErrorType, uri::UriError}, helpers::{api::api_success, nano::{nano_address_to_public_key, nano_to_raw, raw_to_nano}, 
+    qr::qr_response, uri::{build_nano_uri, parse_nano_uri}}, 
+services::{ifenpay::api::ifenpay_api_post, ledger::pool::ledger, nano::{block::queue_account_block, queue::account_queue}, wallet::wallet::{create_wallet_account, get_wallet_data, 
+    resolve_monitored_account, resolve_wallet_account, update_wallet_data}}, structs::{api::{ApiErrorResult, ApiSuccessResult}, 
+block::BlockIntent, wallet::{AccountQueueApi, BalanceResponseApi, CreateAccountRequestApi, 
+    MonitoredAccount, RegisterWalletRequestApi, RegisterWalletResponseApi, SendNanoRequestApi, SendNanoResponseApi, SetRepresentativeRequestApi, SetRepresentativeResponseApi, WalletAccount, WalletAccountBalanceApi, WalletAccountsResponseApi, 
+    WalletData, WalletSelectorApi}, qr::AddressQrQueryApi, uri::SendUriRequestApi}};
 
 
-pub async fn create_wallet_api() -> Result<ApiSuccessResult<WalletData>, ApiErrorResult> {
-    let response = ifenpay_api_get::<WalletData>("/wallet/create", None).await?.data.ok_or_else(|| ApiErrorType::ParseError.to_response())?;
+pub async fn register_wallet_api(address: &str, public_key: &str) -> Result<ApiSuccessResult<RegisterWalletResponseApi>, ApiErrorResult> {
+    let payload = RegisterWalletRequestApi {
+        address: address.to_string(),
+        public_key: public_key.to_string(),
+    };
+    let response = ifenpay_api_post::<RegisterWalletRequestApi, RegisterWalletResponseApi>("/wallet/register", &payload, None).await?.data.ok_or_else(|| ApiErrorType::ParseError.to_response())?;
     
     Ok(api_success(response))
 }
 
-pub async fn get_balance_api() -> Result<ApiSuccessResult<BalanceResponseApi>, ApiErrorResult> {
-    let wallet_data = get_wallet_data();
-    let pending_response = ifenpay_api_get::<BlocksPerAccountResponse>(&format!("/wallet/pending/{}", wallet_data.address), Some(&wallet_data.api_key)).await;
-
-    if pending_response.is_ok(){
-        let data = pending_response.unwrap().data.unwrap();
+pub async fn get_balance_api(Query(selector): Query<WalletSelectorApi>) -> Result<ApiSuccessResult<BalanceResponseApi>, ApiErrorResult> {
+    let wallet_data = get_wallet_data(selector.wallet.as_deref())?;
+    let monitored = resolve_monitored_account(&wallet_data, selector.account.as_deref())?;
 
-        let mut pending_blocks = Vec::new();
+    // Watch-only accounts cannot sign, so their receivable blocks are only reported, never pocketed.
+    if let MonitoredAccount::Owned(account) = &monitored {
+        let pending_response = ledger().receivable(&account.address, &wallet_data.api_key).await;
 
-        if let Some(blocks) = data.blocks.get(&wallet_data.address) {
-            for pending_block in blocks {
-                pending_blocks.push(pending_block.clone());
+        if let Ok(pending_blocks) = pending_response {
+            if !pending_blocks.is_empty() {
+                receive_pending_blocks_api(&wallet_data, account, pending_blocks).await;
             }
-        }
-        if pending_blocks.len() > 0 {
-            receive_pending_blocks_api(&wallet_data, pending_blocks).await;
-        }
 
-        sleep(std::time::Duration::from_secs(1));
+            sleep(std::time::Duration::from_secs(1));
+        }
     }
 
-	let response = ifenpay_api_get::<BalanceResponseApi>(&format!("/wallet/balance/{}", wallet_data.address), Some(&wallet_data.api_key))
-        .await?.data.ok_or_else(|| ApiErrorType::ParseError.to_response())?;
+	let response = ledger().balance(monitored.address(), &wallet_data.api_key).await?;
 
     Ok(api_success(response))
 }
 
-async fn receive_pending_blocks_api(wallet_data: &WalletData, pending_blocks: Vec<String>) {
+pub async fn list_accounts_api(Query(selector): Query<WalletSelectorApi>) -> Result<ApiSuccessResult<WalletAccountsResponseApi>, ApiErrorResult> {
+    let wallet_data = get_wallet_data(selector.wallet.as_deref())?;
+    let mut accounts = Vec::with_capacity(wallet_data.accounts.len());
+
+    for account in &wallet_data.accounts {
+        let balance = ledger().balance(&account.address, &wallet_data.api_key).await.ok();
+
+        accounts.push(WalletAccountBalanceApi {
+            index: account.index,
+            address: account.address.clone(),
+            label: account.label.clone(),
+            balance,
+        });
+    }
+
+    Ok(api_success(WalletAccountsResponseApi {
+        accounts,
+        next_index: wallet_data.next_index,
+    }))
+}
+
+pub async fn create_account_api(Json(payload): Json<CreateAccountRequestApi>) -> Result<ApiSuccessResult<WalletAccount>, ApiErrorResult> {
+    let account = update_wallet_data(payload.wallet.as_deref(), |wallet_data| create_wallet_account(wallet_data, payload.label))?;
+
+    Ok(api_success(account))
+}
+
+async fn receive_pending_blocks_api(wallet_data: &WalletData, account: &WalletAccount, pending_blocks: Vec<String>) {
     let api_key = wallet_data.api_key.clone();
 
     for pending_block in pending_blocks {
-        let block_info_response = ifenpay_api_get::<BlockInfoResponseApi>(&format!("/block/info/{}", pending_block), Some(&api_key)).await;
-        if let Ok(block_info_response) = block_info_response {
-            if let Some(block_info) = block_info_response.data {
-                let pending_ammount_u: u128 = block_info.amount.parse().unwrap_or(0); 
-                let signed_block = prepare_generate_and_sign_block(wallet_data, pending_ammount_u, &pending_block, true).await;
-                if let Ok(signed_block) = signed_block {
-                    let request_payload = PublishBlockRequestApi {
-                        subtype: signed_block.subtype.clone(),
-                        block: signed_block.block.clone(),
-                    };
-                    let _ = ifenpay_api_post::<PublishBlockRequestApi, PublishBlockResponseApi>("/block/publish", &request_payload, Some(&api_key)).await;
-                }
-            }
+        let block_info_response = ledger().block_info(&pending_block, &api_key).await;
+        if let Ok(block_info) = block_info_response {
+            let pending_ammount_u: u128 = block_info.amount.parse().unwrap_or(0); 
+            let _ = queue_account_block(wallet_data, account, BlockIntent::Receive { amount_raw: pending_ammount_u, link: &pending_block }).await;
         }
     }
 }
 
 pub async fn send_nano_api(Json(payload): Json<SendNanoRequestApi>) -> Result<ApiSuccessResult<SendNanoResponseApi>, ApiErrorResult> {
-    let wallet_data = get_wallet_data();    
-    let api_key = wallet_data.api_key.clone();
-    let amount_raw = nano_to_raw(&payload.amount).unwrap();
-    let reciepient_public_key = nano_address_to_public_key(&payload.recipient_address, true)
-        .map_err(|_| AccountError::InvalidAddress.to_response())?;
-
-    let signed_block = prepare_generate_and_sign_block(&wallet_data, amount_raw, &reciepient_public_key, false).await?;
-    let request_payload = PublishBlockRequestApi {
-        subtype: "send".to_string(),
-        block: signed_block.block.clone(),
-    };
-    ifenpay_api_post::<PublishBlockRequestApi, PublishBlockResponseApi>("/block/publish", &request_payload, Some(&api_key)).await?;
+    let amount_raw = nano_to_raw(&payload.amount)?;
+    let response = send_raw_amount(payload.wallet.as_deref(), payload.account.as_deref(), &payload.recipient_address, amount_raw).await?;
 
     Ok(api_success(SendNanoResponseApi {
         amount: payload.amount,
-        recipient: payload.recipient_address,
+        ..response
     }))
-}
\ No newline at end of file
+}
+
+/// Pays a `nano:` URI; `amount` is only used when the URI carries none.
+pub async fn send_uri_api(Json(payload): Json<SendUriRequestApi>) -> Result<ApiSuccessResult<SendNanoResponseApi>, ApiErrorResult> {
+    let uri = parse_nano_uri(&payload.uri)?;
+    let amount_raw = match (uri.amount_raw.as_deref(), payload.amount.as_deref()) {
+        (Some(amount_raw), _) => amount_raw.parse::<u128>().map_err(|_| UriError::InvalidAmount.to_response())?,
+        (None, Some(amount)) => nano_to_raw(amount)?,
+        (None, None) => return Err(UriError::MissingAmount.to_response()),
+    };
+
+    let response = send_raw_amount(payload.wallet.as_deref(), payload.account.as_deref(), &uri.address, amount_raw).await?;
+
+    Ok(api_success(response))
+}
+
+pub async fn set_representative_api(Json(payload): Json<SetRepresentativeRequestApi>) -> Result<ApiSuccessResult<SetRepresentativeResponseApi>, ApiErrorResult> {
+    let wallet_data = get_wallet_data(payload.wallet.as_deref())?;
+    let account = resolve_wallet_account(&wallet_data, payload.account.as_deref())?;
+    let representative_public_key = nano_address_to_public_key(payload.representative.trim(), true)?;
+
+    let (signed_block, attempts, queue_position) = queue_account_block(&wallet_data, &account, BlockIntent::Change { representative_public_key: &representative_public_key }).await?;
+
+    Ok(api_success(SetRepresentativeResponseApi {
+        account: account.address,
+        representative: signed_block.block.representative,
+        hash: signed_block.hash,
+        queue_position,
+        attempts,
+    }))
+}
+
+/// Pending block operations on an account, in the order they will be built and published.
+pub async fn get_account_queue_api(Query(selector): Query<WalletSelectorApi>) -> Result<ApiSuccessResult<AccountQueueApi>, ApiErrorResult> {
+    let wallet_data = get_wallet_data(selector.wallet.as_deref())?;
+    let account = resolve_wallet_account(&wallet_data, selector.account.as_deref())?;
+
+    Ok(api_success(account_queue(&account.address)))
+}
+
+pub async fn get_address_qr_api(Query(query): Query<AddressQrQueryApi>) -> Result<Response, ApiErrorResult> {
+    let wallet_data = get_wallet_data(query.wallet.as_deref())?;
+    let account = resolve_monitored_account(&wallet_data, query.account.as_deref())?;
+    let amount_raw = query.amount.as_deref().map(nano_to_raw).transpose()?;
+    let uri = build_nano_uri(account.address(), amount_raw, query.label.as_deref(), None);
+
+    qr_response(&uri, query.format.unwrap_or_default(), query.size)
+}
+
+async fn send_raw_amount(wallet: Option<&str>, account: Option<&str>, recipient_address: &str, amount_raw: u128) -> Result<SendNanoResponseApi, ApiErrorResult> {
+    let wallet_data = get_wallet_data(wallet)?;
+    let account = resolve_wallet_account(&wallet_data, account)?;
+    let reciepient_public_key = nano_address_to_public_key(recipient_address, true)
+        .map_err(|_| AccountError::InvalidAddress.to_response())?;
+
+    let (signed_block, attempts, queue_position) = queue_account_block(&wallet_data, &account, BlockIntent::Send { amount_raw, link: &reciepient_public_key }).await?;
+
+    Ok(SendNanoResponseApi {
+        account: account.address,
+        amount: raw_to_nano(amount_raw),
+        recipient: recipient_address.to_string(),
+        hash: signed_block.hash,
+        queue_position,
+        attempts,
+    })
+}
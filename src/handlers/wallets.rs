@@ -0,0 +1,82 @@
+use axum::{Json, extract::Path};
+
+use crate::{helpers::api::api_success, services::wallet::{backup::{export_wallet, restore_wallet}, password::rotate_wallet_password, 
+    registry::{create_wallet, delete_wallet, import_wallet, load_wallet_registry, resolve_wallet_name, select_wallet}, 
+    session::{lock_all_wallets, lock_wallet, unlock_wallet}, shares::{recover_wallet_from_shares, split_wallet_seed}}, 
+    structs::{api::{ApiErrorResult, ApiSuccessResult}, wallet::{CreateWalletRequestApi, ExportWalletRequestApi, ExportWalletResponseApi, ImportWalletRequestApi, 
+    LockWalletRequestApi, RecoverWalletRequestApi, RestoreWalletRequestApi, RotatePasswordRequestApi, RotatePasswordResponseApi, SplitSeedRequestApi, 
+    SplitSeedResponseApi, UnlockWalletRequestApi, WalletRegistry, WalletRegistryEntry, WalletSessionStatusApi}}};
+
+pub async fn list_wallets_api() -> Result<ApiSuccessResult<WalletRegistry>, ApiErrorResult> {
+    Ok(api_success(load_wallet_registry()?))
+}
+
+pub async fn create_wallet_api(Json(payload): Json<CreateWalletRequestApi>) -> Result<ApiSuccessResult<WalletRegistryEntry>, ApiErrorResult> {
+    let entry = create_wallet(payload.name.trim()).await?;
+
+    Ok(api_success(entry))
+}
+
+pub async fn import_wallet_api(Json(payload): Json<ImportWalletRequestApi>) -> Result<ApiSuccessResult<WalletRegistryEntry>, ApiErrorResult> {
+    let entry = import_wallet(&payload).await?;
+
+    Ok(api_success(entry))
+}
+
+pub async fn select_wallet_api(Path(name): Path<String>) -> Result<ApiSuccessResult<WalletRegistry>, ApiErrorResult> {
+    let registry = select_wallet(&name)?;
+
+    Ok(api_success(registry))
+}
+
+pub async fn delete_wallet_api(Path(name): Path<String>) -> Result<ApiSuccessResult<WalletRegistry>, ApiErrorResult> {
+    let registry = delete_wallet(&name)?;
+
+    Ok(api_success(registry))
+}
+
+pub async fn rotate_password_api(Json(payload): Json<RotatePasswordRequestApi>) -> Result<ApiSuccessResult<RotatePasswordResponseApi>, ApiErrorResult> {
+    let result = rotate_wallet_password(&payload.current_password, &payload.new_password)?;
+
+    Ok(api_success(result))
+}
+
+pub async fn unlock_wallet_api(Json(payload): Json<UnlockWalletRequestApi>) -> Result<ApiSuccessResult<WalletSessionStatusApi>, ApiErrorResult> {
+    let name = resolve_wallet_name(payload.wallet.as_deref())?;
+    let status = unlock_wallet(&name, &payload.password)?;
+
+    Ok(api_success(status))
+}
+
+pub async fn lock_wallet_api(Json(payload): Json<LockWalletRequestApi>) -> Result<ApiSuccessResult<Vec<WalletSessionStatusApi>>, ApiErrorResult> {
+    let statuses = match payload.wallet.as_deref() {
+        Some(wallet) => vec![lock_wallet(&resolve_wallet_name(Some(wallet))?)],
+        None => lock_all_wallets()?,
+    };
+
+    Ok(api_success(statuses))
+}
+
+pub async fn export_wallet_api(Path(name): Path<String>, Json(payload): Json<ExportWalletRequestApi>) -> Result<ApiSuccessResult<ExportWalletResponseApi>, ApiErrorResult> {
+    let export = export_wallet(&name, &payload.wallet_password, payload.password.as_deref())?;
+
+    Ok(api_success(export))
+}
+
+pub async fn restore_wallet_api(Json(payload): Json<RestoreWalletRequestApi>) -> Result<ApiSuccessResult<WalletRegistryEntry>, ApiErrorResult> {
+    let entry = restore_wallet(&payload)?;
+
+    Ok(api_success(entry))
+}
+
+pub async fn split_wallet_seed_api(Path(name): Path<String>, Json(payload): Json<SplitSeedRequestApi>) -> Result<ApiSuccessResult<SplitSeedResponseApi>, ApiErrorResult> {
+    let result = split_wallet_seed(&name, &payload.password, payload.threshold, payload.shares)?;
+
+    Ok(api_success(result))
+}
+
+pub async fn recover_wallet_api(Json(payload): Json<RecoverWalletRequestApi>) -> Result<ApiSuccessResult<WalletRegistryEntry>, ApiErrorResult> {
+    let entry = recover_wallet_from_shares(&payload).await?;
+
+    Ok(api_success(entry))
+}
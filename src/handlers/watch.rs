@@ -0,0 +1,63 @@
+use axum::{Json, extract::Query};
+
+use crate::{helpers::api::api_success, services::{ledger::pool::ledger, wallet::wallet::{add_watch_only_account, 
+    get_wallet_data, remove_watch_only_account, resolve_monitored_account, update_wallet_data}}, structs::{api::{ApiErrorResult, ApiSuccessResult}, 
+    wallet::{AccountHistoryQueryApi, AccountHistoryResponseApi, AddWatchOnlyRequestApi, ReceivableBlocksResponseApi, 
+    RemoveWatchOnlyRequestApi, WalletSelectorApi, WatchOnlyAccount, WatchOnlyAccountBalanceApi, WatchOnlyAccountsResponseApi}}};
+
+const DEFAULT_HISTORY_COUNT: u32 = 20;
+const MAX_HISTORY_COUNT: u32 = 500;
+
+pub async fn list_watch_only_api(Query(selector): Query<WalletSelectorApi>) -> Result<ApiSuccessResult<WatchOnlyAccountsResponseApi>, ApiErrorResult> {
+    let wallet_data = get_wallet_data(selector.wallet.as_deref())?;
+    let mut accounts = Vec::with_capacity(wallet_data.watch_only.len());
+
+    for account in &wallet_data.watch_only {
+        let balance = ledger().balance(&account.address, &wallet_data.api_key).await.ok();
+
+        accounts.push(WatchOnlyAccountBalanceApi {
+            address: account.address.clone(),
+            label: account.label.clone(),
+            balance,
+        });
+    }
+
+    Ok(api_success(WatchOnlyAccountsResponseApi { accounts }))
+}
+
+pub async fn add_watch_only_api(Json(payload): Json<AddWatchOnlyRequestApi>) -> Result<ApiSuccessResult<WatchOnlyAccount>, ApiErrorResult> {
+    let account = update_wallet_data(payload.wallet.as_deref(), |wallet_data| add_watch_only_account(wallet_data, &payload.address, payload.label))?;
+
+    Ok(api_success(account))
+}
+
+pub async fn remove_watch_only_api(Json(payload): Json<RemoveWatchOnlyRequestApi>) -> Result<ApiSuccessResult<WatchOnlyAccount>, ApiErrorResult> {
+    let account = update_wallet_data(payload.wallet.as_deref(), |wallet_data| remove_watch_only_account(wallet_data, &payload.address))?;
+
+    Ok(api_success(account))
+}
+
+/// Lists receivable block hashes without receiving them, for owned and watch-only accounts alike.
+pub async fn get_receivable_api(Query(selector): Query<WalletSelectorApi>) -> Result<ApiSuccessResult<ReceivableBlocksResponseApi>, ApiErrorResult> {
+    let wallet_data = get_wallet_data(selector.wallet.as_deref())?;
+    let monitored = resolve_monitored_account(&wallet_data, selector.account.as_deref())?;
+    let address = monitored.address().to_string();
+
+    let blocks = ledger().receivable(&address, &wallet_data.api_key).await?;
+
+    Ok(api_success(ReceivableBlocksResponseApi {
+        account: address,
+        watch_only: monitored.is_watch_only(),
+        blocks,
+    }))
+}
+
+pub async fn get_account_history_api(Query(query): Query<AccountHistoryQueryApi>) -> Result<ApiSuccessResult<AccountHistoryResponseApi>, ApiErrorResult> {
+    let wallet_data = get_wallet_data(query.wallet.as_deref())?;
+    let monitored = resolve_monitored_account(&wallet_data, query.account.as_deref())?;
+    let count = query.count.unwrap_or(DEFAULT_HISTORY_COUNT).clamp(1, MAX_HISTORY_COUNT);
+
+    let response = ledger().account_history(monitored.address(), count, &wallet_data.api_key).await?;
+
+    Ok(api_success(response))
+}
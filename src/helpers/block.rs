@@ -0,0 +1,102 @@
+use blake2::Blake2bVar;
+use blake2::digest::{Update, VariableOutput};
+
+use crate::enums::api::error::block::BlockError;
+use crate::helpers::nano::{hex_to_bytes, nano_address_to_public_key, public_key_to_nano_address};
+use crate::structs::api::ApiErrorResult;
+use crate::structs::block::{LegacyBlock, StateBlock};
+
+/// Canonical binary state block as used by the node protocol:
+/// `account(32) || previous(32) || representative(32) || balance(16, big-endian) || link(32) || signature(64) || work(8, big-endian)`.
+/// The 32-byte hashing preamble (`0x...06`) is not part of it; the hash covers the preamble and the first 176 bytes.
+pub const STATE_BLOCK_SIZE: usize = 216;
+
+const ACCOUNT: std::ops::Range<usize> = 0..32;
+const PREVIOUS: std::ops::Range<usize> = 32..64;
+const REPRESENTATIVE: std::ops::Range<usize> = 64..96;
+const BALANCE: std::ops::Range<usize> = 96..112;
+const LINK: std::ops::Range<usize> = 112..144;
+const SIGNATURE: std::ops::Range<usize> = 144..208;
+const WORK: std::ops::Range<usize> = 208..216;
+
+pub fn encode_state_block(block: &StateBlock) -> Result<[u8; STATE_BLOCK_SIZE], ApiErrorResult> {
+    if block.block_type != "state" {
+        return Err(BlockError::InvalidBlockType.to_response());
+    }
+
+    let mut bytes = [0u8; STATE_BLOCK_SIZE];
+    bytes[ACCOUNT].copy_from_slice(&address_bytes(&block.account).ok_or_else(|| BlockError::InvalidAccountAddress.to_response())?);
+    bytes[PREVIOUS].copy_from_slice(&fixed_hex(&block.previous, 32).ok_or_else(|| BlockError::InvalidPreviousHash.to_response())?);
+    bytes[REPRESENTATIVE].copy_from_slice(&address_bytes(&block.representative).ok_or_else(|| BlockError::InvalidRepresentativeAddress.to_response())?);
+    bytes[BALANCE].copy_from_slice(&block.balance.trim().parse::<u128>().map_err(|_| BlockError::InvalidBalance.to_response())?.to_be_bytes());
+    bytes[LINK].copy_from_slice(&fixed_hex(&block.link, 32).ok_or_else(|| BlockError::InvalidLink.to_response())?);
+    bytes[SIGNATURE].copy_from_slice(&fixed_hex(&block.signature, 64).ok_or_else(|| BlockError::MalformedSignature.to_response())?);
+    bytes[WORK].copy_from_slice(&fixed_hex(&block.work, 8).ok_or_else(|| BlockError::InvalidWorkLength.to_response())?);
+
+    Ok(bytes)
+}
+
+pub fn decode_state_block(bytes: &[u8]) -> Result<StateBlock, ApiErrorResult> {
+    if bytes.len() != STATE_BLOCK_SIZE {
+        return Err(BlockError::InvalidBlockLength.to_response());
+    }
+
+    let balance = u128::from_be_bytes(bytes[BALANCE].try_into().map_err(|_| BlockError::InvalidBalance.to_response())?);
+
+    Ok(StateBlock {
+        block_type: "state".to_string(),
+        account: public_key_to_nano_address(&hex::encode(&bytes[ACCOUNT]))
+            .map_err(|_| BlockError::InvalidAccountAddress.to_response())?,
+        previous: hex::encode_upper(&bytes[PREVIOUS]),
+        representative: public_key_to_nano_address(&hex::encode(&bytes[REPRESENTATIVE]))
+            .map_err(|_| BlockError::InvalidRepresentativeAddress.to_response())?,
+        balance: balance.to_string(),
+        link: hex::encode_upper(&bytes[LINK]),
+        signature: hex::encode_upper(&bytes[SIGNATURE]),
+        work: hex::encode(&bytes[WORK]),
+    })
+}
+
+/// Hash of a legacy block: blake2b-256 over its hashed fields, without the state block preamble.
+/// send: `previous || destination || balance(16)`, receive: `previous || source`,
+/// open: `source || representative || account`, change: `previous || representative`.
+pub fn legacy_block_hash(block: &LegacyBlock) -> Result<Vec<u8>, ApiErrorResult> {
+    let previous_bytes = |previous: &str| fixed_hex(previous, 32).ok_or_else(|| BlockError::InvalidPreviousHash.to_response());
+    let source_bytes = |source: &str| fixed_hex(source, 32).ok_or_else(|| BlockError::InvalidLink.to_response());
+    let representative_bytes = |representative: &str| address_bytes(representative)
+        .ok_or_else(|| BlockError::InvalidRepresentativeAddress.to_response());
+
+    let fields = match block {
+        LegacyBlock::Send { previous, destination, .. } => {
+            let balance = block.balance().ok_or_else(|| BlockError::InvalidBalance.to_response())?;
+            vec![
+                previous_bytes(previous)?,
+                address_bytes(destination).ok_or_else(|| BlockError::InvalidAccountAddress.to_response())?,
+                balance.to_be_bytes().to_vec(),
+            ]
+        }
+        LegacyBlock::Receive { previous, source, .. } => vec![previous_bytes(previous)?, source_bytes(source)?],
+        LegacyBlock::Open { source, representative, account, .. } => vec![
+            source_bytes(source)?,
+            representative_bytes(representative)?,
+            address_bytes(account).ok_or_else(|| BlockError::InvalidAccountAddress.to_response())?,
+        ],
+        LegacyBlock::Change { previous, representative, .. } => vec![previous_bytes(previous)?, representative_bytes(representative)?],
+    };
+
+    let mut hasher = Blake2bVar::new(32).map_err(|_| BlockError::BlockHashGenerationFailed.to_response())?;
+    fields.iter().for_each(|field| hasher.update(field));
+    let mut hash = vec![0u8; 32];
+    hasher.finalize_variable(&mut hash).map_err(|_| BlockError::BlockHashGenerationFailed.to_response())?;
+
+    Ok(hash)
+}
+
+fn address_bytes(address: &str) -> Option<Vec<u8>> {
+    let public_key = nano_address_to_public_key(address, true).ok()?;
+    fixed_hex(&public_key, 32)
+}
+
+fn fixed_hex(value: &str, length: usize) -> Option<Vec<u8>> {
+    hex_to_bytes(value).ok().filter(|bytes| bytes.len() == length)
+}
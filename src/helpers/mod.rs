@@ -1,2 +1,6 @@
 pub mod api;
 pub mod nano;
+pub mod shamir;
+pub mod uri;
+pub mod qr;
+pub mod block;
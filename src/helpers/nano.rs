@@ -1,12 +1,17 @@
+use bip39::{Language, Mnemonic};
 use blake2::Blake2bVar;
 use blake2::digest::{Update, VariableOutput};
 use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsPoint};
 use curve25519_dalek::scalar::Scalar;
 use hex::FromHex;
+use hmac::{Hmac, Mac};
+use sha2::Sha512;
 
 use crate::enums::api::error::account::AccountError;
 use crate::enums::api::error::transaction::TransactionError;
 use crate::structs::api::ApiErrorResult;
+use crate::structs::util::AddressValidationReason;
 
 fn blake2b512(data: &[u8]) -> Result<[u8; 64], String> {
     let mut hasher = Blake2bVar::new(64).map_err(|e| format!("Invalid blake2b size: {}", e))?;
@@ -79,6 +84,20 @@ pub fn nano_to_raw(nano_str: &str) -> Result<u128, ApiErrorResult> {
     Ok(raw)
 }
 
+/// Formats a raw amount as a Nano decimal string without trailing zeros.
+pub fn raw_to_nano(raw: u128) -> String {
+    const RAW_PER_NANO: u128 = 1_000_000_000_000_000_000_000_000_000_000;
+
+    let whole = raw / RAW_PER_NANO;
+    let fractional = raw % RAW_PER_NANO;
+    if fractional == 0 {
+        return whole.to_string();
+    }
+
+    let fractional = format!("{:030}", fractional);
+    format!("{}.{}", whole, fractional.trim_end_matches('0'))
+}
+
 pub fn hex_to_bytes(h: &str) -> Result<Vec<u8>, String> {
     let h = h.trim().to_lowercase().replace("0x", "");
     Vec::from_hex(&h).map_err(|e| format!("Invalid hex: {}", e))
@@ -126,12 +145,16 @@ pub fn nano_account_private_key(seed_hex: &str, index: u32) -> Result<Vec<u8>, S
 
 pub fn nano_account_public_key(seed_hex: &str, index: u32) -> Result<Vec<u8>, String> {
     let priv32 = nano_account_private_key(seed_hex, index)?;
+    nano_private_key_to_public_key(&priv32)
+}
+
+pub fn nano_private_key_to_public_key(priv32: &[u8]) -> Result<Vec<u8>, String> {
     if priv32.len() != 32 {
         return Err("Private key must be 32 bytes".to_string());
     }
 
     let mut priv_arr = [0u8; 32];
-    priv_arr.copy_from_slice(&priv32);
+    priv_arr.copy_from_slice(priv32);
     let h = blake2b512(&priv_arr)?;
 
     let mut scalar_bytes = [0u8; 32];
@@ -181,21 +204,20 @@ pub fn state_block_hash(
     Ok(out.to_vec())
 }
 
-pub fn sign_hash_with_seed(seed_hex: &str, index: u32, h32: &[u8]) -> Result<String, String> {
-    let priv32 = nano_account_private_key(seed_hex, index)?;
+pub fn sign_hash_with_private_key(priv32: &[u8], h32: &[u8]) -> Result<String, String> {
     if priv32.len() != 32 {
         return Err("Private key must be 32 bytes".to_string());
     }
 
     let mut priv_arr = [0u8; 32];
-    priv_arr.copy_from_slice(&priv32);
+    priv_arr.copy_from_slice(priv32);
     let h = blake2b512(&priv_arr)?;
 
     let mut a_bytes = [0u8; 32];
     a_bytes.copy_from_slice(&h[..32]);
     let a = Scalar::from_bytes_mod_order(clamp_scalar_bytes(a_bytes));
 
-    let public_key = nano_account_public_key(seed_hex, index)?;
+    let public_key = nano_private_key_to_public_key(priv32)?;
 
     let mut nonce_input = Vec::with_capacity(32 + h32.len());
     nonce_input.extend_from_slice(&h[32..64]);
@@ -222,6 +244,86 @@ pub fn sign_hash_with_seed(seed_hex: &str, index: u32, h32: &[u8]) -> Result<Str
     Ok(hex::encode(sig))
 }
 
+/// Verifies an ed25519-blake2b signature as produced by `sign_hash_with_private_key`.
+pub fn verify_hash_signature(pub32: &[u8], h32: &[u8], sig64: &[u8]) -> Result<bool, String> {
+    if pub32.len() != 32 {
+        return Err("Public key must be 32 bytes".to_string());
+    }
+    if sig64.len() != 64 {
+        return Err("Signature must be 64 bytes".to_string());
+    }
+
+    let public_point = CompressedEdwardsY::from_slice(pub32)
+        .map_err(|e| e.to_string())?
+        .decompress()
+        .ok_or("Public key is not a valid curve point")?;
+
+    let mut s_bytes = [0u8; 32];
+    s_bytes.copy_from_slice(&sig64[32..]);
+    let Some(s) = Option::<Scalar>::from(Scalar::from_canonical_bytes(s_bytes)) else {
+        return Ok(false);
+    };
+
+    let mut k_input = Vec::with_capacity(64 + h32.len());
+    k_input.extend_from_slice(&sig64[..32]);
+    k_input.extend_from_slice(pub32);
+    k_input.extend_from_slice(h32);
+    let k = Scalar::from_bytes_mod_order_wide(&blake2b512(&k_input)?);
+
+    // s·B - k·A must equal R.
+    let r_point = EdwardsPoint::vartime_double_scalar_mul_basepoint(&k, &-public_point, &s);
+    Ok(r_point.compress().as_bytes() == &sig64[..32])
+}
+
+/// Prepended to the message before hashing, so a message hash can never collide with a real block link.
+const NANO_MESSAGE_PREFIX: &[u8] = b"Nano Signed Message:\n";
+
+/// Hash signed for an off-chain message: a state block for `account_pub32` with zero `previous`, the burn address as
+/// representative, zero balance and `blake2b(prefix || message)` as link. Such a block can never be published, so the
+/// signature cannot be replayed on the ledger, and hardware wallets that only sign blocks can produce it too.
+pub fn nano_message_hash(account_pub32: &[u8], message: &str) -> Result<Vec<u8>, String> {
+    let mut hasher = Blake2bVar::new(32).map_err(|e| format!("Invalid blake2b size: {}", e))?;
+    hasher.update(NANO_MESSAGE_PREFIX);
+    hasher.update(message.as_bytes());
+    let mut link = [0u8; 32];
+    hasher
+        .finalize_variable(&mut link)
+        .map_err(|e| format!("Failed to finalize blake2b: {}", e))?;
+
+    let account = account_pub32.to_vec();
+    state_block_hash(&account, &vec![0u8; 32], &vec![0u8; 32], 0, &link.to_vec())
+}
+
+pub fn bip39_mnemonic_to_seed(mnemonic: &str, passphrase: &str) -> Result<Vec<u8>, String> {
+    let normalized = mnemonic.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+    let mnemonic = Mnemonic::parse_in(Language::English, &normalized)
+        .map_err(|e| format!("Invalid mnemonic: {}", e))?;
+
+    Ok(mnemonic.to_seed(passphrase).to_vec())
+}
+
+/// SLIP-0010 Ed25519 derivation along m/44'/165'/index', as used by Ledger and Nault.
+pub fn bip44_nano_private_key(bip39_seed: &[u8], index: u32) -> Result<Vec<u8>, String> {
+    const HARDENED: u32 = 0x8000_0000;
+
+    let mut mac = Hmac::<Sha512>::new_from_slice(b"ed25519 seed").map_err(|e| e.to_string())?;
+    Mac::update(&mut mac, bip39_seed);
+    let mut node = mac.finalize().into_bytes();
+
+    for segment in [44, 165, index] {
+        let mut data = Vec::with_capacity(37);
+        data.push(0u8);
+        data.extend_from_slice(&node[..32]);
+        data.extend_from_slice(&(segment | HARDENED).to_be_bytes());
+
+        let mut mac = Hmac::<Sha512>::new_from_slice(&node[32..]).map_err(|e| e.to_string())?;
+        Mac::update(&mut mac, &data);
+        node = mac.finalize().into_bytes();
+    }
+
+    Ok(node[..32].to_vec())
+}
+
 const NANO_ALPH: &[u8; 32] = b"13456789abcdefghijkmnopqrstuwxyz";
 
 pub fn nano_base32_encode(data: &[u8]) -> String {
@@ -325,6 +427,26 @@ pub fn public_key_to_nano_address(public_key_hex: &str) -> Result<String, String
     Ok(format!("nano_{}{}", key_part, chk_part))
 }
 
+/// Validates an address and returns its `nano_` form and public key, or the first reason it is invalid.
+pub fn validate_nano_address(address: &str) -> Result<(String, String), AddressValidationReason> {
+    let address = address.trim();
+    let body = address.strip_prefix("nano_")
+        .or_else(|| address.strip_prefix("xrb_"))
+        .ok_or(AddressValidationReason::MissingPrefix)?;
+
+    if body.len() != 60 {
+        return Err(AddressValidationReason::InvalidLength);
+    }
+    // The 52-character key part carries 4 padding bits, so it must start with '1' or '3'.
+    if !body.bytes().all(|c| NANO_ALPH.contains(&c)) || !body.starts_with(['1', '3']) {
+        return Err(AddressValidationReason::InvalidCharacter);
+    }
+
+    let public_key = nano_address_to_public_key(address, true).map_err(|_| AddressValidationReason::InvalidChecksum)?;
+
+    Ok((format!("nano_{}", body), public_key.to_uppercase()))
+}
+
 pub fn nano_address_to_public_key(address: &str, verify_checksum: bool) -> Result<String, ApiErrorResult> {
     let address = address.trim();
     let body = if address.starts_with("nano_") {
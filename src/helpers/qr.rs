@@ -0,0 +1,52 @@
+use std::io::Cursor;
+
+use axum::{http::header, response::{IntoResponse, Response}};
+use image::{ImageFormat, Luma};
+use qrcode::{QrCode, render::svg};
+
+use crate::enums::api::error::qr::QrError;
+use crate::structs::api::ApiErrorResult;
+use crate::structs::qr::QrFormat;
+
+pub const DEFAULT_QR_SIZE: u32 = 256;
+const MIN_QR_SIZE: u32 = 64;
+const MAX_QR_SIZE: u32 = 2048;
+
+/// Renders `data` as a QR code. `size` is the minimum width in pixels and is ignored for ASCII output.
+pub fn render_qr(data: &str, format: QrFormat, size: Option<u32>) -> Result<Vec<u8>, ApiErrorResult> {
+    let size = size.unwrap_or(DEFAULT_QR_SIZE);
+    if !(MIN_QR_SIZE..=MAX_QR_SIZE).contains(&size) {
+        return Err(QrError::InvalidSize.to_response());
+    }
+
+    let code = QrCode::new(data.as_bytes()).map_err(|_| QrError::DataTooLong.to_response())?;
+
+    match format {
+        QrFormat::Svg => Ok(code.render::<svg::Color>()
+            .min_dimensions(size, size)
+            .build()
+            .into_bytes()),
+        QrFormat::Png => {
+            let image = code.render::<Luma<u8>>()
+                .min_dimensions(size, size)
+                .build();
+            let mut bytes = Cursor::new(Vec::new());
+            image.write_to(&mut bytes, ImageFormat::Png).map_err(|_| QrError::RenderFailed.to_response())?;
+
+            Ok(bytes.into_inner())
+        }
+        // Two characters per module keep the code roughly square in a terminal.
+        QrFormat::Ascii => Ok(code.render::<char>()
+            .module_dimensions(2, 1)
+            .dark_color('#')
+            .light_color(' ')
+            .build()
+            .into_bytes()),
+    }
+}
+
+pub fn qr_response(data: &str, format: QrFormat, size: Option<u32>) -> Result<Response, ApiErrorResult> {
+    let bytes = render_qr(data, format, size)?;
+
+    Ok(([(header::CONTENT_TYPE, format.content_type())], bytes).into_response())
+}
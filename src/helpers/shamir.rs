@@ -0,0 +1,140 @@
+use std::fmt;
+
+use aes_gcm::aead::OsRng;
+use aes_gcm::aead::rand_core::RngCore;
+use zeroize::Zeroizing;
+
+use crate::helpers::nano::hex_to_bytes;
+
+/// A single Shamir share: the evaluation point `x` (1..=255) and one `y` byte per secret byte.
+/// `threshold` shares rebuild the seed, so `y` is wiped on drop and never printed by `Debug`.
+#[derive(Clone, PartialEq)]
+pub struct SecretShare {
+    pub threshold: u8,
+    pub x: u8,
+    pub y: Zeroizing<Vec<u8>>,
+}
+
+impl fmt::Debug for SecretShare {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SecretShare")
+            .field("threshold", &self.threshold)
+            .field("x", &self.x)
+            .field("y", &"[REDACTED]")
+            .finish()
+    }
+}
+
+impl SecretShare {
+    /// Printable form `<threshold>-<x>-<HEX>`, e.g. `3-1-9F0E...`.
+    pub fn encode(&self) -> String {
+        format!("{}-{}-{}", self.threshold, self.x, hex::encode_upper(&self.y))
+    }
+
+    pub fn parse(share: &str) -> Result<Self, String> {
+        let mut parts = share.trim().splitn(3, '-');
+        let threshold = parts.next().and_then(|part| part.parse::<u8>().ok()).ok_or("Invalid share threshold")?;
+        let x = parts.next().and_then(|part| part.parse::<u8>().ok()).ok_or("Invalid share index")?;
+        let y = Zeroizing::new(hex_to_bytes(parts.next().ok_or("Missing share data")?)?);
+
+        if threshold < 2 || x == 0 || y.is_empty() {
+            return Err("Invalid share".to_string());
+        }
+
+        Ok(SecretShare { threshold, x, y })
+    }
+}
+
+/// Multiplication in GF(2^8) with the AES reduction polynomial x^8 + x^4 + x^3 + x + 1.
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0u8;
+    while b != 0 {
+        if b & 1 != 0 {
+            product ^= a;
+        }
+        let carry = a & 0x80 != 0;
+        a <<= 1;
+        if carry {
+            a ^= 0x1b;
+        }
+        b >>= 1;
+    }
+    product
+}
+
+/// Multiplicative inverse via a^254; `a` must be non-zero.
+fn gf_inv(a: u8) -> u8 {
+    let mut result = 1u8;
+    let mut base = a;
+    let mut exponent = 254u8;
+    while exponent != 0 {
+        if exponent & 1 != 0 {
+            result = gf_mul(result, base);
+        }
+        base = gf_mul(base, base);
+        exponent >>= 1;
+    }
+    result
+}
+
+/// Splits `secret` into `share_count` shares of which any `threshold` reconstruct it.
+pub fn split_secret(secret: &[u8], threshold: u8, share_count: u8) -> Result<Vec<SecretShare>, String> {
+    if threshold < 2 || threshold > share_count {
+        return Err("Threshold must be at least 2 and at most the share count".to_string());
+    }
+    if secret.is_empty() {
+        return Err("Secret is empty".to_string());
+    }
+
+    let mut shares: Vec<SecretShare> = (1..=share_count)
+        // Sized up front so pushing never reallocates and leaves an unwiped copy behind.
+        .map(|x| SecretShare { threshold, x, y: Zeroizing::new(Vec::with_capacity(secret.len())) })
+        .collect();
+
+    // Coefficients a1..a(k-1) of a fresh random polynomial per secret byte; a0 is the byte itself.
+    let mut coefficients = Zeroizing::new(vec![0u8; threshold as usize]);
+    for &byte in secret {
+        coefficients[0] = byte;
+        OsRng.fill_bytes(&mut coefficients[1..]);
+
+        for share in shares.iter_mut() {
+            let y = coefficients.iter().rev().fold(0u8, |acc, &coefficient| gf_mul(acc, share.x) ^ coefficient);
+            share.y.push(y);
+        }
+    }
+
+    Ok(shares)
+}
+
+/// Reconstructs the secret by Lagrange interpolation at x = 0.
+pub fn combine_shares(shares: &[SecretShare]) -> Result<Zeroizing<Vec<u8>>, String> {
+    let first = shares.first().ok_or("No shares provided")?;
+    if shares.len() < first.threshold as usize {
+        return Err(format!("At least {} shares are required", first.threshold));
+    }
+    for (position, share) in shares.iter().enumerate() {
+        if share.threshold != first.threshold || share.y.len() != first.y.len() {
+            return Err("Shares belong to different secrets".to_string());
+        }
+        if shares[..position].iter().any(|other| other.x == share.x) {
+            return Err("Duplicate share index".to_string());
+        }
+    }
+
+    let mut secret = Zeroizing::new(vec![0u8; first.y.len()]);
+    for share in shares {
+        let mut numerator = 1u8;
+        let mut denominator = 1u8;
+        for other in shares.iter().filter(|other| other.x != share.x) {
+            numerator = gf_mul(numerator, other.x);
+            denominator = gf_mul(denominator, other.x ^ share.x);
+        }
+        let basis = gf_mul(numerator, gf_inv(denominator));
+
+        for (byte, &y) in secret.iter_mut().zip(share.y.iter()) {
+            *byte ^= gf_mul(y, basis);
+        }
+    }
+
+    Ok(secret)
+}
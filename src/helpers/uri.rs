@@ -0,0 +1,92 @@
+use crate::enums::api::error::{account::AccountError, uri::UriError};
+use crate::helpers::nano::{raw_to_nano, validate_nano_address};
+use crate::structs::api::ApiErrorResult;
+use crate::structs::uri::NanoUriApi;
+
+const NANO_URI_SCHEME: &str = "nano:";
+
+/// Parses a `nano:` payment URI. Unknown query parameters are ignored; the address is normalized to `nano_`.
+pub fn parse_nano_uri(uri: &str) -> Result<NanoUriApi, ApiErrorResult> {
+    let uri = uri.trim();
+    let rest = uri.get(..NANO_URI_SCHEME.len())
+        .filter(|scheme| scheme.eq_ignore_ascii_case(NANO_URI_SCHEME))
+        .map(|_| &uri[NANO_URI_SCHEME.len()..])
+        .ok_or_else(|| UriError::InvalidScheme.to_response())?;
+
+    let (address, query) = rest.split_once('?').unwrap_or((rest, ""));
+    let (address, _) = validate_nano_address(address).map_err(|_| AccountError::InvalidAddress.to_response())?;
+
+    let mut parsed = NanoUriApi {
+        address,
+        amount_raw: None,
+        amount: None,
+        label: None,
+        message: None,
+    };
+
+    for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        let value = percent_decode(value)?;
+        match key {
+            "amount" => {
+                let amount_raw = value.parse::<u128>().map_err(|_| UriError::InvalidAmount.to_response())?;
+                parsed.amount = Some(raw_to_nano(amount_raw));
+                parsed.amount_raw = Some(amount_raw.to_string());
+            }
+            "label" => parsed.label = Some(value),
+            "message" => parsed.message = Some(value),
+            _ => {}
+        }
+    }
+
+    Ok(parsed)
+}
+
+/// Builds a `nano:` URI; `address` must already be validated.
+pub fn build_nano_uri(address: &str, amount_raw: Option<u128>, label: Option<&str>, message: Option<&str>) -> String {
+    let params: Vec<String> = [
+        amount_raw.map(|amount| format!("amount={}", amount)),
+        label.map(|label| format!("label={}", percent_encode(label))),
+        message.map(|message| format!("message={}", percent_encode(message))),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    if params.is_empty() {
+        format!("{}{}", NANO_URI_SCHEME, address)
+    } else {
+        format!("{}{}?{}", NANO_URI_SCHEME, address, params.join("&"))
+    }
+}
+
+/// RFC 3986 percent-encoding of everything except unreserved characters.
+fn percent_encode(value: &str) -> String {
+    value.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (byte as char).to_string(),
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+fn percent_decode(value: &str) -> Result<String, ApiErrorResult> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut position = 0;
+
+    while position < bytes.len() {
+        if bytes[position] == b'%' {
+            let byte = value.get(position + 1..position + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                .ok_or_else(|| UriError::InvalidEncoding.to_response())?;
+            decoded.push(byte);
+            position += 3;
+        } else {
+            decoded.push(bytes[position]);
+            position += 1;
+        }
+    }
+
+    String::from_utf8(decoded).map_err(|_| UriError::InvalidEncoding.to_response())
+}
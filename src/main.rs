@@ -4,7 +4,7 @@ use tokio::sync::Mutex;
 
 use tokio::spawn;
 
-use crate::services::{app::initialize::initialize_rust_app, mcp::initialize_mcp_stdio_server, routers::router::initialize_api_routers};
+use crate::services::{app::{cli::run_cli_command, initialize::initialize_rust_app}, wallet::session::run_auto_lock_task, mcp::initialize_mcp_stdio_server, routers::router::initialize_api_routers};
 
 mod handlers;
 mod services;
@@ -15,8 +15,14 @@ mod enums;
 
 #[tokio::main]
 async fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some((command, command_args)) = args.split_first() {
+        std::process::exit(run_cli_command(command, command_args).await);
+    }
+
     initialize_rust_app().await;
 
+    spawn(run_auto_lock_task());
     spawn(initialize_api_routers());
 
     initialize_mcp_stdio_server(tokio::runtime::Handle::current());
@@ -0,0 +1,104 @@
+use std::io::{BufRead, Write};
+
+use dotenvy::dotenv;
+
+use crate::services::app::initialize::acquire_instance_lock;
+use crate::services::wallet::password::{rotate_wallet_password, wallet_password};
+use crate::services::wallet::registry::WALLETS_DIR;
+use crate::services::wallet::shares::recover_wallet_from_shares;
+use crate::structs::api::ApiErrorResult;
+use crate::structs::wallet::RecoverWalletRequestApi;
+
+const USAGE: &str = "Usage: nano-toolset [rotate-password | recover-wallet <name> <address>]";
+
+/// Runs a one-shot maintenance command instead of the servers and returns the process exit code.
+pub async fn run_cli_command(command: &str, args: &[String]) -> i32 {
+    dotenv().ok();
+
+    match (command, args) {
+        ("rotate-password", []) => rotate_password_command(),
+        ("recover-wallet", [name, address]) => recover_wallet_command(name, address).await,
+        _ => {
+            eprintln!("{}", USAGE);
+            2
+        }
+    }
+}
+
+/// Reads the new password from `NEW_AI_WALLET_PASSWORD` or prompts for it twice on stdin.
+/// Refuses to run next to a server, which would keep saving wallets under the old password.
+fn rotate_password_command() -> i32 {
+    if let Err(error) = acquire_instance_lock() {
+        eprintln!("❌ Stop the running server first ({}), or rotate via POST /wallets/password/rotate", error);
+        return 1;
+    }
+
+    let new_password = match std::env::var("NEW_AI_WALLET_PASSWORD") {
+        Ok(password) => password,
+        Err(_) => {
+            let first = prompt_line("New wallet password: ");
+            let second = prompt_line("Repeat new wallet password: ");
+            if first != second {
+                eprintln!("❌ Passwords do not match");
+                return 1;
+            }
+            first
+        }
+    };
+
+    match rotate_wallet_password(&wallet_password(), &new_password) {
+        Ok(result) => {
+            println!("✅ Re-encrypted wallets: {}", result.rotated_wallets.join(", "));
+            println!("✅ Updated AI_WALLET_PASSWORD in .env");
+            0
+        }
+        Err(error) => {
+            eprintln!("❌ Password rotation failed: {}", error_message(error));
+            1
+        }
+    }
+}
+
+/// Reads Shamir shares from stdin, one per line, until an empty line and stores the recovered wallet.
+async fn recover_wallet_command(name: &str, address: &str) -> i32 {
+    println!("Enter shares, one per line, followed by an empty line:");
+    let shares: Vec<String> = std::iter::from_fn(|| Some(prompt_line("> ")))
+        .take_while(|line| !line.trim().is_empty())
+        .collect();
+
+    if std::fs::create_dir_all(WALLETS_DIR).is_err() {
+        eprintln!("❌ Failed to create {}", WALLETS_DIR);
+        return 1;
+    }
+
+    let request = RecoverWalletRequestApi {
+        name: name.to_string(),
+        shares,
+        address: address.to_string(),
+        seed_type: None,
+        accounts: None,
+    };
+    match recover_wallet_from_shares(&request).await {
+        Ok(entry) => {
+            println!("✅ Recovered wallet '{}' ({})", entry.name, entry.address);
+            0
+        }
+        Err(error) => {
+            eprintln!("❌ Wallet recovery failed: {}", error_message(error));
+            1
+        }
+    }
+}
+
+fn error_message((_, response): ApiErrorResult) -> String {
+    response.0.error.map(|error| error.message).unwrap_or_default()
+}
+
+fn prompt_line(prompt: &str) -> String {
+    print!("{}", prompt);
+    let _ = std::io::stdout().flush();
+
+    let mut line = String::new();
+    let _ = std::io::stdin().lock().read_line(&mut line);
+    line.trim_end_matches(['\r', '\n']).to_string()
+}
@@ -1,12 +1,38 @@
-use std::fs;
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::io::Write;
 use std::path::Path;
+use std::sync::OnceLock;
 use dotenvy::dotenv;
 use rand::{rng};
 use rand::distr::{Distribution, Uniform};
 
-use crate::handlers::wallet::create_wallet_api;
-use crate::services::wallet::wallet::encrypt_wallet_data;
+use crate::services::wallet::registry::initialize_wallet_registry;
+use crate::services::wallet::session::unlock_wallets_on_start;
 
+const INSTANCE_LOCK_FILE_PATH: &str = "data/instance.lock";
+
+/// Held until the process exits; the OS releases it then, also after a crash.
+static INSTANCE_LOCK: OnceLock<File> = OnceLock::new();
+
+/// Locks `data/instance.lock` for this process and writes its pid into it. Fails while another server or maintenance
+/// command works on the same data directory, since each keeps the wallet password in memory.
+pub fn acquire_instance_lock() -> Result<(), String> {
+	fs::create_dir_all("data").map_err(|error| error.to_string())?;
+	let mut file = OpenOptions::new()
+		.create(true)
+		.truncate(false)
+		.write(true)
+		.open(INSTANCE_LOCK_FILE_PATH)
+		.map_err(|error| error.to_string())?;
+	file.try_lock().map_err(|error| match error {
+		TryLockError::WouldBlock => format!("another nano-toolset process holds {}", INSTANCE_LOCK_FILE_PATH),
+		TryLockError::Error(error) => error.to_string(),
+	})?;
+	let _ = file.set_len(0).and_then(|_| write!(file, "{}", std::process::id()));
+	let _ = INSTANCE_LOCK.set(file);
+
+	Ok(())
+}
 
 pub async fn initialize_rust_app() {
 	let env_file = Path::new(".env");
@@ -43,17 +69,32 @@ pub async fn initialize_rust_app() {
 	} 
 	dotenv().ok();
 
+	acquire_instance_lock().expect("Failed to lock the data directory");
+	initialize_wallet_registry().await;
+	unlock_wallets_on_start();
+}
+pub fn set_env_file_value(key: &str, value: &str) -> std::io::Result<()> {
+	let env_file = Path::new(".env");
+	let content = fs::read_to_string(env_file).unwrap_or_default();
+	let entry = format!("{}={}", key, value);
 
-	let wallets_dir = Path::new("data");
-	fs::create_dir_all(wallets_dir).expect("Failed to create wallets directory");
-
-	let wallet_file = wallets_dir.join("ai.bin");
-	if !wallet_file.exists() {
-		let wallet_data = create_wallet_api().await.expect("Failed to create wallet via API").0.data.expect("Failed to parse wallet data");
-		let wallet_password = std::env::var("AI_WALLET_PASSWORD").expect("AI_WALLET_PASSWORD not set in .env");
-		let encrypted_wallet = encrypt_wallet_data(&wallet_data, &wallet_password).expect("Failed to encrypt wallet data");
-
-		fs::write(&wallet_file, &encrypted_wallet).expect("Failed to create data/ai.bin");
-		println!("✅ Created data/ai.bin");
+	let mut replaced = false;
+	let mut lines: Vec<String> = content
+		.lines()
+		.map(|line| {
+			if line.trim_start().starts_with(&format!("{}=", key)) {
+				replaced = true;
+				entry.clone()
+			} else {
+				line.to_string()
+			}
+		})
+		.collect();
+	if !replaced {
+		lines.push(entry);
 	}
-}
\ No newline at end of file
+
+	let tmp_file = env_file.with_extension("tmp");
+	fs::write(&tmp_file, lines.join("\n") + "\n")?;
+	fs::rename(&tmp_file, env_file)
+}
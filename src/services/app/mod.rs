@@ -1 +1,2 @@
-pub mod initialize;
\ No newline at end of file
+pub mod initialize;
+pub mod cli;
\ No newline at end of file
@@ -7,13 +7,25 @@ use serde::de::DeserializeOwned;
 use crate::enums::api::error::api::ApiErrorType;
 use crate::structs::api::{ApiError, ApiErrorResult, ApiResponse};
 
+pub fn ifenpay_api_url() -> String {
+    env::var("IFENPAY_API_URL").unwrap_or_else(|_| "http://127.0.0.1:3000".to_string())
+}
+
 pub async fn ifenpay_api_post<P, T>(uri: &str, payload: &P, api_key: Option<&str>) -> Result<ApiResponse<T>, ApiErrorResult>
+where
+    P: Serialize + ?Sized,
+    T: DeserializeOwned,
+{
+    ifenpay_api_post_to(&ifenpay_api_url(), uri, payload, api_key).await
+}
+
+/// Like `ifenpay_api_post`, against an explicit API base URL.
+pub async fn ifenpay_api_post_to<P, T>(api_url: &str, uri: &str, payload: &P, api_key: Option<&str>) -> Result<ApiResponse<T>, ApiErrorResult>
 where
     P: Serialize + ?Sized,
     T: DeserializeOwned,
 {
     let client = reqwest::Client::new();
-    let api_url = env::var("IFENPAY_API_URL").unwrap_or_else(|_| "http://127.0.0.1:3000".to_string());
     let mut request = client.post(&format!("{}{}", api_url, uri)).json(payload);
     if let Some(key) = api_key {
         request = request.header("X-API-Key", key);
@@ -25,11 +37,18 @@ where
 }
 
 pub async fn ifenpay_api_get<T>(uri: &str, api_key: Option<&str>) -> Result<ApiResponse<T>, ApiErrorResult>
+where
+    T: DeserializeOwned,
+{
+    ifenpay_api_get_from(&ifenpay_api_url(), uri, api_key).await
+}
+
+/// Like `ifenpay_api_get`, against an explicit API base URL.
+pub async fn ifenpay_api_get_from<T>(api_url: &str, uri: &str, api_key: Option<&str>) -> Result<ApiResponse<T>, ApiErrorResult>
 where
     T: DeserializeOwned,
 {
     let client = reqwest::Client::new();
-    let api_url = env::var("IFENPAY_API_URL").unwrap_or_else(|_| "http://127.0.0.1:3000".to_string());
     let mut request = client.get(&format!("{}{}", api_url, uri));
     if let Some(key) = api_key {
         request = request.header("X-API-Key", key);
@@ -0,0 +1,175 @@
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use crate::{enums::api::error::{api::ApiErrorType, node::NodeRpcError}, helpers::nano::raw_to_nano,
+    services::{ifenpay::api::{ifenpay_api_get_from, ifenpay_api_post_to}, ledger::node::{node_list, node_rpc_call}, nano::validation::{BASE_THRESH_RECEIVE_OPEN_EPOCH, BASE_THRESH_SEND_CHANGE}},
+    structs::{api::ApiErrorResult, block::{BlockInfoResponseApi, PublishBlockRequestApi, PublishBlockResponseApi},
+    wallet::{AccountHistoryResponseApi, AccountInfoResponseApi, BalanceResponseApi, BlocksPerAccountResponse}}};
+
+const RECEIVABLE_COUNT: u32 = 100;
+
+/// One ledger endpoint. Payments, credits and wallet registration always use the ifenpay API.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LedgerBackend {
+    /// ifenpay API at the given base URL.
+    Ifenpay(String),
+    /// Direct RPC to a Nano node at the given URL. Needs no API key.
+    NodeRpc(String),
+}
+
+#[derive(Debug, Deserialize)]
+struct NodeAccountInfo {
+    frontier: String,
+    balance: String,
+    representative: String,
+    block_count: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NodeAccountBalance {
+    balance: String,
+    /// Newer nodes send `receivable` next to the deprecated `pending`; older ones only `pending`.
+    receivable: Option<String>,
+    pending: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NodeProcessResponse {
+    hash: String,
+}
+
+impl LedgerBackend {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            LedgerBackend::Ifenpay(_) => "ifenpay",
+            LedgerBackend::NodeRpc(_) => "node",
+        }
+    }
+
+    pub fn url(&self) -> &str {
+        match self {
+            LedgerBackend::Ifenpay(url) | LedgerBackend::NodeRpc(url) => url,
+        }
+    }
+
+    pub async fn account_info(&self, address: &str, api_key: &str) -> Result<AccountInfoResponseApi, ApiErrorResult> {
+        match self {
+            LedgerBackend::Ifenpay(api_url) => ifenpay_api_get_from::<AccountInfoResponseApi>(api_url, &format!("/account/info/{}", address), Some(api_key))
+                .await?.data.ok_or_else(|| ApiErrorType::ParseError.to_response()),
+            LedgerBackend::NodeRpc(rpc_url) => {
+                let info: NodeAccountInfo = node_rpc_call(rpc_url, json!({
+                    "action": "account_info",
+                    "account": address,
+                    "representative": "true",
+                })).await?;
+
+                Ok(AccountInfoResponseApi {
+                    frontier: info.frontier,
+                    balance: info.balance,
+                    representative: info.representative,
+                    difficulty_send: format!("{:016x}", BASE_THRESH_SEND_CHANGE),
+                    difficulty_receive: format!("{:016x}", BASE_THRESH_RECEIVE_OPEN_EPOCH),
+                    block_count: info.block_count,
+                })
+            }
+        }
+    }
+
+    pub async fn balance(&self, address: &str, api_key: &str) -> Result<BalanceResponseApi, ApiErrorResult> {
+        match self {
+            LedgerBackend::Ifenpay(api_url) => ifenpay_api_get_from::<BalanceResponseApi>(api_url, &format!("/wallet/balance/{}", address), Some(api_key))
+                .await?.data.ok_or_else(|| ApiErrorType::ParseError.to_response()),
+            LedgerBackend::NodeRpc(rpc_url) => {
+                let balance: NodeAccountBalance = node_rpc_call(rpc_url, json!({
+                    "action": "account_balance",
+                    "account": address,
+                })).await?;
+                let balance_raw = parse_raw(&balance.balance)?;
+                let pending_raw = parse_raw(balance.receivable.or(balance.pending).as_deref().unwrap_or("0"))?;
+
+                Ok(BalanceResponseApi {
+                    account: address.to_string(),
+                    balance: raw_to_nano(balance_raw),
+                    balance_raw: balance_raw.to_string(),
+                    pending: raw_to_nano(pending_raw),
+                    pending_raw: pending_raw.to_string(),
+                })
+            }
+        }
+    }
+
+    /// Hashes of send blocks waiting to be received by `address`.
+    pub async fn receivable(&self, address: &str, api_key: &str) -> Result<Vec<String>, ApiErrorResult> {
+        match self {
+            LedgerBackend::Ifenpay(api_url) => Ok(ifenpay_api_get_from::<BlocksPerAccountResponse>(api_url, &format!("/wallet/pending/{}", address), Some(api_key))
+                .await?.data
+                .and_then(|data| data.blocks.get(address).cloned())
+                .unwrap_or_default()),
+            LedgerBackend::NodeRpc(rpc_url) => {
+                let mut response: Value = node_rpc_call(rpc_url, json!({
+                    "action": "receivable",
+                    "account": address,
+                    "count": RECEIVABLE_COUNT.to_string(),
+                })).await?;
+
+                node_list(response.get_mut("blocks").map(Value::take))
+            }
+        }
+    }
+
+    pub async fn block_info(&self, hash: &str, api_key: &str) -> Result<BlockInfoResponseApi, ApiErrorResult> {
+        match self {
+            LedgerBackend::Ifenpay(api_url) => ifenpay_api_get_from::<BlockInfoResponseApi>(api_url, &format!("/block/info/{}", hash.trim()), Some(api_key))
+                .await?.data.ok_or_else(|| ApiErrorType::ParseError.to_response()),
+            LedgerBackend::NodeRpc(rpc_url) => node_rpc_call(rpc_url, json!({
+                "action": "block_info",
+                "hash": hash.trim(),
+                "json_block": "true",
+            })).await,
+        }
+    }
+
+    pub async fn publish(&self, request: &PublishBlockRequestApi, api_key: &str) -> Result<(), ApiErrorResult> {
+        match self {
+            LedgerBackend::Ifenpay(api_url) => {
+                ifenpay_api_post_to::<PublishBlockRequestApi, PublishBlockResponseApi>(api_url, "/block/publish", request, Some(api_key)).await?;
+            }
+            LedgerBackend::NodeRpc(rpc_url) => {
+                let response: NodeProcessResponse = node_rpc_call(rpc_url, json!({
+                    "action": "process",
+                    "json_block": "true",
+                    "subtype": request.subtype,
+                    "block": request.block,
+                })).await?;
+                if response.hash.is_empty() {
+                    return Err(NodeRpcError::InvalidResponse.to_response());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub async fn account_history(&self, address: &str, count: u32, api_key: &str) -> Result<AccountHistoryResponseApi, ApiErrorResult> {
+        match self {
+            LedgerBackend::Ifenpay(api_url) => ifenpay_api_get_from::<AccountHistoryResponseApi>(api_url, &format!("/wallet/history/{}?count={}", address, count), Some(api_key))
+                .await?.data.ok_or_else(|| ApiErrorType::ParseError.to_response()),
+            LedgerBackend::NodeRpc(rpc_url) => {
+                let mut response: Value = node_rpc_call(rpc_url, json!({
+                    "action": "account_history",
+                    "account": address,
+                    "count": count.to_string(),
+                })).await?;
+
+                Ok(AccountHistoryResponseApi {
+                    account: address.to_string(),
+                    history: node_list(response.get_mut("history").map(Value::take))?,
+                })
+            }
+        }
+    }
+}
+
+fn parse_raw(value: &str) -> Result<u128, ApiErrorResult> {
+    value.trim().parse::<u128>().map_err(|_| NodeRpcError::InvalidResponse.to_response())
+}
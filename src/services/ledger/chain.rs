@@ -0,0 +1,181 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use once_cell::sync::Lazy;
+
+use crate::{helpers::nano::{nano_address_to_public_key, public_key_to_nano_address}, services::{nano::validation::ZERO_HASH, wallet::wallet::write_file_atomic}, structs::{block::SignedBlock, ledger::AccountChainState, wallet::AccountInfoResponseApi}};
+
+const CHAIN_STATE_FILE_PATH: &str = "data/chain_state.json";
+const MAX_LAGGING_FRONTIERS: usize = 16;
+/// How long after our last published block a backend may still report an older frontier. Past this, the block is
+/// taken to have never reached the ledger and the backend's view wins again.
+const LAGGING_STATE_TTL_SECS: u64 = 120;
+
+/// Chain state per account address, loaded once from `path` and written back on every change.
+pub struct ChainStateStore {
+    path: PathBuf,
+    states: Mutex<BTreeMap<String, AccountChainState>>,
+}
+
+static CHAIN_STATES: Lazy<Arc<ChainStateStore>> = Lazy::new(|| Arc::new(ChainStateStore::open(CHAIN_STATE_FILE_PATH)));
+
+#[cfg(test)]
+tokio::task_local! {
+    /// Replaces the process-wide store inside `scope`, so tests never read or write `data/chain_state.json`.
+    pub static TEST_CHAIN_STATES: Arc<ChainStateStore>;
+}
+
+fn chain_states() -> Arc<ChainStateStore> {
+    #[cfg(test)]
+    if let Ok(store) = TEST_CHAIN_STATES.try_with(Arc::clone) {
+        return store;
+    }
+
+    Arc::clone(&CHAIN_STATES)
+}
+
+/// Makes a block we just published the head of its account chain.
+pub fn record_published_block(signed_block: &SignedBlock) {
+    let store = chain_states();
+    let mut states = store.states.lock().unwrap();
+    advance_chain_state(&mut states, signed_block, unix_timestamp());
+    store.save(&states);
+}
+
+/// Picks the state to build the next block on from the backend's view (`None` when the account is unknown to it)
+/// and the cache, and stores the result. `None` means the account has no blocks.
+pub fn reconcile_chain_state(address: &str, account_info: Option<&AccountInfoResponseApi>) -> Option<AccountChainState> {
+    let store = chain_states();
+    let mut states = store.states.lock().unwrap();
+    let Some(reconciled) = reconciled_chain_state(states.get(address), account_info, unix_timestamp()) else {
+        if states.remove(address).is_some() {
+            store.save(&states);
+        }
+        return None;
+    };
+    let changed = states.get(address)
+        .is_none_or(|cached| *cached != AccountChainState { updated_at: cached.updated_at, ..reconciled.clone() });
+    if changed {
+        states.insert(address.to_string(), reconciled.clone());
+        store.save(&states);
+    }
+
+    Some(reconciled)
+}
+
+/// Replaces the cached state with the backend's view (`None` when the account is unknown to it), e.g. after the cache
+/// built a block that forked the chain or that never reached the ledger.
+pub fn reset_chain_state(address: &str, account_info: Option<&AccountInfoResponseApi>) -> Option<AccountChainState> {
+    let store = chain_states();
+    let mut states = store.states.lock().unwrap();
+    let state = reconciled_chain_state(None, account_info, unix_timestamp());
+    match &state {
+        Some(state) => states.insert(address.to_string(), state.clone()),
+        None => states.remove(address),
+    };
+    store.save(&states);
+
+    state
+}
+
+/// The canonical `nano_` address of the block's account, which is what wallet reads look the cache up by. Presigned
+/// blocks may spell the account with an `xrb_` prefix or different casing.
+fn chain_state_key(signed_block: &SignedBlock) -> String {
+    public_key_to_nano_address(&signed_block.account_public_key)
+        .ok()
+        .or_else(|| nano_address_to_public_key(&signed_block.block.account.to_lowercase(), false).ok()
+            .and_then(|public_key| public_key_to_nano_address(&public_key).ok()))
+        .unwrap_or_else(|| signed_block.block.account.clone())
+}
+
+pub fn advance_chain_state(states: &mut BTreeMap<String, AccountChainState>, signed_block: &SignedBlock, now: u64) {
+    let block = &signed_block.block;
+    let key = chain_state_key(signed_block);
+    let previous = block.previous.to_uppercase();
+    let current = states.get(&key).filter(|state| state.frontier.eq_ignore_ascii_case(&previous));
+
+    let height = if previous == ZERO_HASH {
+        Some(1)
+    } else {
+        current.and_then(|state| state.height).map(|height| height + 1)
+    };
+    let mut lagging_frontiers = current.map(|state| state.lagging_frontiers.clone()).unwrap_or_default();
+    lagging_frontiers.push(previous);
+    if lagging_frontiers.len() > MAX_LAGGING_FRONTIERS {
+        lagging_frontiers.drain(..lagging_frontiers.len() - MAX_LAGGING_FRONTIERS);
+    }
+
+    states.insert(key, AccountChainState {
+        frontier: signed_block.hash.to_uppercase(),
+        balance: block.balance.clone(),
+        representative: block.representative.clone(),
+        height,
+        lagging_frontiers,
+        updated_at: now,
+    });
+}
+
+/// The backend wins unless it still reports a frontier we have already built on, i.e. it has not seen our latest blocks yet.
+/// The cache only wins for `LAGGING_STATE_TTL_SECS` after our last block and while the backend's chain is shorter than ours.
+/// An account the backend does not know keeps its cached state for the same time, e.g. right after its open block.
+pub fn reconciled_chain_state(cached: Option<&AccountChainState>, account_info: Option<&AccountInfoResponseApi>, now: u64) -> Option<AccountChainState> {
+    let Some(account_info) = account_info else {
+        return cached.filter(|cached| now.saturating_sub(cached.updated_at) <= LAGGING_STATE_TTL_SECS).cloned();
+    };
+
+    let backend_height = account_info.block_count.as_deref()
+        .and_then(|block_count| block_count.trim().parse::<u64>().ok());
+    if let Some(cached) = cached
+        && now.saturating_sub(cached.updated_at) <= LAGGING_STATE_TTL_SECS
+        && !matches!((backend_height, cached.height), (Some(backend_height), Some(height)) if backend_height >= height)
+        && cached.lagging_frontiers.iter().any(|frontier| frontier.eq_ignore_ascii_case(&account_info.frontier)) {
+        return Some(cached.clone());
+    }
+
+    let height = backend_height
+        .or_else(|| cached
+            .filter(|cached| cached.frontier.eq_ignore_ascii_case(&account_info.frontier))
+            .and_then(|cached| cached.height));
+
+    Some(AccountChainState {
+        frontier: account_info.frontier.to_uppercase(),
+        balance: account_info.balance.clone(),
+        representative: account_info.representative.clone(),
+        height,
+        lagging_frontiers: Vec::new(),
+        updated_at: now,
+    })
+}
+
+impl ChainStateStore {
+    pub fn open(path: impl Into<PathBuf>) -> ChainStateStore {
+        let path = path.into();
+        let states = fs::read(&path)
+            .ok()
+            .and_then(|content| serde_json::from_slice(&content).ok())
+            .unwrap_or_default();
+
+        ChainStateStore { path, states: Mutex::new(states) }
+    }
+
+    /// The cache only saves round trips, so a failed write is logged instead of failing the request.
+    /// Written atomically, so a crash cannot leave a truncated file that would drop every cached state on the next start.
+    fn save(&self, states: &BTreeMap<String, AccountChainState>) {
+        let result = serde_json::to_vec_pretty(states)
+            .map_err(|error| error.to_string())
+            .and_then(|content| write_file_atomic(&self.path, &content).map_err(|error| error.to_string()));
+        if let Err(error) = result {
+            eprintln!("⚠️  Failed to save {}: {}", self.path.display(), error);
+        }
+    }
+}
+
+fn unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
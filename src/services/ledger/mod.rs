@@ -0,0 +1,4 @@
+pub mod backend;
+pub mod chain;
+pub mod node;
+pub mod pool;
\ No newline at end of file
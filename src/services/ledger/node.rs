@@ -0,0 +1,35 @@
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::enums::api::error::node::NodeRpcError;
+use crate::structs::api::ApiErrorResult;
+
+/// Posts an RPC action to a Nano node. Nodes report failures as `{"error": "..."}` with HTTP 200.
+pub async fn node_rpc_call<T>(rpc_url: &str, payload: Value) -> Result<T, ApiErrorResult>
+where
+    T: DeserializeOwned,
+{
+    let client = reqwest::Client::new();
+    let resp = client.post(rpc_url).json(&payload).send().await
+        .map_err(|_| NodeRpcError::Unreachable.to_response())?;
+    let response = resp.json::<Value>().await
+        .map_err(|_| NodeRpcError::InvalidResponse.to_response())?;
+
+    if let Some(error) = response.get("error").and_then(Value::as_str) {
+        return Err(NodeRpcError::Rejected(error.to_string()).to_response());
+    }
+
+    serde_json::from_value(response).map_err(|_| NodeRpcError::InvalidResponse.to_response())
+}
+
+/// Empty lists come back as `""` from some node versions instead of `[]`.
+pub fn node_list<T>(value: Option<Value>) -> Result<Vec<T>, ApiErrorResult>
+where
+    T: DeserializeOwned,
+{
+    match value {
+        None | Some(Value::Null) => Ok(Vec::new()),
+        Some(Value::String(text)) if text.is_empty() => Ok(Vec::new()),
+        Some(value) => serde_json::from_value(value).map_err(|_| NodeRpcError::InvalidResponse.to_response()),
+    }
+}
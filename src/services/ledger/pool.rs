@@ -0,0 +1,388 @@
+use std::cmp::Reverse;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use once_cell::sync::Lazy;
+use tokio::task::JoinSet;
+
+use crate::{enums::api::error::{api::ApiErrorType, node::NodeRpcError}, services::{ifenpay::api::ifenpay_api_url, ledger::backend::LedgerBackend},
+    structs::{api::ApiErrorResult, block::{BlockInfoResponseApi, PublishBlockRequestApi}, ledger::{LedgerBackendStatusApi, LedgerRequestRecordApi, LedgerStatusResponseApi},
+    wallet::{AccountHistoryResponseApi, AccountInfoResponseApi, BalanceResponseApi}}};
+
+const DEFAULT_NODE_RPC_URL: &str = "http://127.0.0.1:7076";
+/// Consecutive transport failures after which a backend drops behind the healthy ones.
+const UNHEALTHY_AFTER_FAILURES: u32 = 3;
+/// An unhealthy backend is tried first again once its last failure is this old.
+const UNHEALTHY_RETRY_SECS: u64 = 30;
+const MAX_RECENT_REQUESTS: usize = 50;
+const MAX_PUBLISHED_HASHES: usize = 256;
+/// Node `process` error for a block already in its ledger.
+const NODE_OLD_BLOCK: &str = "Old block";
+/// Node `account_info` error for an account without blocks.
+const NODE_ACCOUNT_NOT_FOUND: &str = "Account not found";
+/// Node `block_info` error for a block it does not have.
+const NODE_BLOCK_NOT_FOUND: &str = "Block not found";
+/// Message prefix of the ifenpay API's `RPC_RESPONSE_ERROR`, followed by the node's error.
+const IFENPAY_RPC_ERROR_PREFIX: &str = "RPC response indicates an error: ";
+
+static LEDGER_POOL: Lazy<LedgerPool> = Lazy::new(|| LedgerPool::new(configured_backends()));
+
+pub fn ledger() -> &'static LedgerPool {
+    &LEDGER_POOL
+}
+
+#[derive(Debug, Clone)]
+struct BackendHealth {
+    score: u32,
+    successes: u64,
+    failures: u64,
+    consecutive_failures: u32,
+    last_latency_ms: Option<u64>,
+    last_error: Option<String>,
+    last_success_at: Option<u64>,
+    last_failure_at: Option<u64>,
+}
+
+impl Default for BackendHealth {
+    fn default() -> Self {
+        BackendHealth {
+            score: 100,
+            successes: 0,
+            failures: 0,
+            consecutive_failures: 0,
+            last_latency_ms: None,
+            last_error: None,
+            last_success_at: None,
+            last_failure_at: None,
+        }
+    }
+}
+
+impl BackendHealth {
+    fn is_healthy(&self, now: u64) -> bool {
+        self.consecutive_failures < UNHEALTHY_AFTER_FAILURES
+            || self.last_failure_at.is_none_or(|failed_at| now.saturating_sub(failed_at) >= UNHEALTHY_RETRY_SECS)
+    }
+}
+
+/// Ordered ledger backends. Reads fail over to the next backend when one is unreachable; blocks are published to all of them.
+pub struct LedgerPool {
+    backends: Vec<LedgerBackend>,
+    health: Mutex<Vec<BackendHealth>>,
+    recent_requests: Mutex<VecDeque<LedgerRequestRecordApi>>,
+    published_hashes: Mutex<VecDeque<String>>,
+}
+
+impl LedgerPool {
+    pub fn new(backends: Vec<LedgerBackend>) -> LedgerPool {
+        LedgerPool {
+            health: Mutex::new(vec![BackendHealth::default(); backends.len()]),
+            backends,
+            recent_requests: Mutex::new(VecDeque::new()),
+            published_hashes: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Whether any backend is an ifenpay API, which needs a registered wallet API key.
+    pub fn uses_ifenpay(&self) -> bool {
+        self.backends.iter().any(|backend| matches!(backend, LedgerBackend::Ifenpay(_)))
+    }
+
+    pub async fn account_info(&self, address: &str, api_key: &str) -> Result<AccountInfoResponseApi, ApiErrorResult> {
+        self.read("account_info", |backend| backend.account_info(address, api_key)).await
+    }
+
+    pub async fn balance(&self, address: &str, api_key: &str) -> Result<BalanceResponseApi, ApiErrorResult> {
+        self.read("balance", |backend| backend.balance(address, api_key)).await
+    }
+
+    pub async fn receivable(&self, address: &str, api_key: &str) -> Result<Vec<String>, ApiErrorResult> {
+        self.read("receivable", |backend| backend.receivable(address, api_key)).await
+    }
+
+    pub async fn block_info(&self, hash: &str, api_key: &str) -> Result<BlockInfoResponseApi, ApiErrorResult> {
+        self.read("block_info", |backend| backend.block_info(hash, api_key)).await
+    }
+
+    pub async fn account_history(&self, address: &str, count: u32, api_key: &str) -> Result<AccountHistoryResponseApi, ApiErrorResult> {
+        self.read("account_history", |backend| backend.account_history(address, count, api_key)).await
+    }
+
+    /// Publishes to every backend at once. Succeeds when at least one accepts the block or already knows it;
+    /// a block accepted earlier is not sent again.
+    pub async fn publish(&'static self, request: &PublishBlockRequestApi, hash: &str, api_key: &str) -> Result<(), ApiErrorResult> {
+        let hash = hash.to_lowercase();
+        if self.published_hashes.lock().unwrap().contains(&hash) {
+            self.push_record("publish", "local", "duplicate", None, 0);
+            return Ok(());
+        }
+
+        let mut tasks = JoinSet::new();
+        let mut task_backends = HashMap::new();
+        for (index, backend) in self.backends.iter().enumerate() {
+            let request = request.clone();
+            let api_key = api_key.to_string();
+            let task = tasks.spawn(async move { backend.publish(&request, &api_key).await });
+            task_backends.insert(task.id(), (index, Instant::now()));
+        }
+
+        let mut accepted = false;
+        let mut rejection = None;
+        let mut failure = None;
+        while let Some(joined) = tasks.join_next_with_id().await {
+            let (task, result) = match joined {
+                Ok((task, result)) => (task, result),
+                // A publish task that panicked counts as a failed attempt on its backend; the others are still collected.
+                Err(error) => (error.id(), Err(ApiErrorType::RequestError.to_response())),
+            };
+            let Some(&(index, started)) = task_backends.get(&task) else {
+                continue;
+            };
+            match result {
+                Ok(()) => {
+                    self.record_attempt(index, "publish", "served", None, started);
+                    accepted = true;
+                }
+                Err(error) if is_duplicate_publish(&error) => {
+                    self.record_attempt(index, "publish", "duplicate", None, started);
+                    accepted = true;
+                }
+                Err(error) if is_transport_failure(&error) => {
+                    self.record_attempt(index, "publish", "failed", Some(&error), started);
+                    failure.get_or_insert(error);
+                }
+                Err(error) => {
+                    self.record_attempt(index, "publish", "rejected", Some(&error), started);
+                    rejection.get_or_insert(error);
+                }
+            }
+        }
+
+        if accepted {
+            let mut published_hashes = self.published_hashes.lock().unwrap();
+            published_hashes.push_back(hash);
+            while published_hashes.len() > MAX_PUBLISHED_HASHES {
+                published_hashes.pop_front();
+            }
+            return Ok(());
+        }
+
+        Err(rejection.or(failure).unwrap_or_else(|| ApiErrorType::RequestError.to_response()))
+    }
+
+    pub fn status(&self) -> LedgerStatusResponseApi {
+        let now = unix_timestamp();
+        let health = self.health.lock().unwrap();
+
+        LedgerStatusResponseApi {
+            backends: self.backends.iter().zip(health.iter()).enumerate()
+                .map(|(position, (backend, health))| LedgerBackendStatusApi {
+                    position,
+                    kind: backend.kind().to_string(),
+                    url: backend.url().to_string(),
+                    healthy: health.is_healthy(now),
+                    score: health.score,
+                    successes: health.successes,
+                    failures: health.failures,
+                    consecutive_failures: health.consecutive_failures,
+                    last_latency_ms: health.last_latency_ms,
+                    last_error: health.last_error.clone(),
+                    last_success_at: health.last_success_at,
+                    last_failure_at: health.last_failure_at,
+                })
+                .collect(),
+            recent_requests: self.recent_requests.lock().unwrap().iter().rev().cloned().collect(),
+        }
+    }
+
+    /// Tries backends in `read_order` until one answers. Only transport failures move on to the next backend;
+    /// an error the backend itself returned (unknown account, bad hash, ...) is passed through.
+    async fn read<'a, T, F, Fut>(&'a self, operation: &str, call: F) -> Result<T, ApiErrorResult>
+    where
+        F: Fn(&'a LedgerBackend) -> Fut,
+        Fut: Future<Output = Result<T, ApiErrorResult>>,
+    {
+        let mut last_error = None;
+        for index in self.read_order() {
+            let started = Instant::now();
+            match call(&self.backends[index]).await {
+                Ok(response) => {
+                    self.record_attempt(index, operation, "served", None, started);
+                    return Ok(response);
+                }
+                Err(error) if is_transport_failure(&error) => {
+                    self.record_attempt(index, operation, "failed", Some(&error), started);
+                    last_error = Some(error);
+                }
+                Err(error) => {
+                    self.record_attempt(index, operation, "rejected", Some(&error), started);
+                    return Err(error);
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| ApiErrorType::RequestError.to_response()))
+    }
+
+    /// Healthy backends in configured order, then unhealthy ones by score.
+    fn read_order(&self) -> Vec<usize> {
+        let now = unix_timestamp();
+        let health = self.health.lock().unwrap();
+        let (mut order, mut unhealthy): (Vec<usize>, Vec<usize>) = (0..self.backends.len())
+            .partition(|&index| health[index].is_healthy(now));
+        unhealthy.sort_by_key(|&index| Reverse(health[index].score));
+        order.extend(unhealthy);
+
+        order
+    }
+
+    fn record_attempt(&self, index: usize, operation: &str, outcome: &str, error: Option<&ApiErrorResult>, started: Instant) {
+        let now = unix_timestamp();
+        let latency_ms = started.elapsed().as_millis() as u64;
+        let error = error.map(error_text);
+
+        {
+            let mut health = self.health.lock().unwrap();
+            let health = &mut health[index];
+            health.last_latency_ms = Some(latency_ms);
+            if outcome == "failed" {
+                health.score = health.score * 4 / 5;
+                health.failures += 1;
+                health.consecutive_failures += 1;
+                health.last_error = error.clone();
+                health.last_failure_at = Some(now);
+            } else {
+                health.score = (health.score * 4 + 100) / 5;
+                health.successes += 1;
+                health.consecutive_failures = 0;
+                health.last_success_at = Some(now);
+            }
+        }
+
+        let backend = &self.backends[index];
+        self.push_record(operation, &format!("{} {}", backend.kind(), backend.url()), outcome, error, latency_ms);
+    }
+
+    fn push_record(&self, operation: &str, backend: &str, outcome: &str, error: Option<String>, latency_ms: u64) {
+        let mut recent_requests = self.recent_requests.lock().unwrap();
+        recent_requests.push_back(LedgerRequestRecordApi {
+            at: unix_timestamp(),
+            operation: operation.to_string(),
+            backend: backend.to_string(),
+            outcome: outcome.to_string(),
+            error,
+            latency_ms,
+        });
+        while recent_requests.len() > MAX_RECENT_REQUESTS {
+            recent_requests.pop_front();
+        }
+    }
+}
+
+/// Parses `NANO_LEDGER_BACKENDS`: comma-separated `ifenpay[:<url>]` or `node[:<url>]` entries in order of preference.
+pub fn parse_backend_list(value: &str) -> Vec<LedgerBackend> {
+    value.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .filter_map(|entry| {
+            let (kind, url) = entry.split_once(':').unwrap_or((entry, ""));
+            let url = url.trim();
+            match kind.trim().to_lowercase().as_str() {
+                "ifenpay" => Some(LedgerBackend::Ifenpay(if url.is_empty() { ifenpay_api_url() } else { url.to_string() })),
+                "node" => Some(LedgerBackend::NodeRpc(if url.is_empty() { node_rpc_url() } else { url.to_string() })),
+                _ => {
+                    eprintln!("⚠️  Ignoring unknown ledger backend '{}' in NANO_LEDGER_BACKENDS", entry);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// `NANO_LEDGER_BACKENDS` when set, otherwise the single backend chosen by `NANO_LEDGER_BACKEND`.
+fn configured_backends() -> Vec<LedgerBackend> {
+    let backends = parse_backend_list(&std::env::var("NANO_LEDGER_BACKENDS").unwrap_or_default());
+    if !backends.is_empty() {
+        return backends;
+    }
+
+    let configured = std::env::var("NANO_LEDGER_BACKEND").unwrap_or_default();
+    match configured.trim().to_lowercase().as_str() {
+        "" | "ifenpay" => vec![LedgerBackend::Ifenpay(ifenpay_api_url())],
+        "node" => vec![LedgerBackend::NodeRpc(node_rpc_url())],
+        other => {
+            eprintln!("⚠️  Unknown NANO_LEDGER_BACKEND '{}' - using ifenpay", other);
+            vec![LedgerBackend::Ifenpay(ifenpay_api_url())]
+        }
+    }
+}
+
+fn node_rpc_url() -> String {
+    std::env::var("NANO_NODE_RPC_URL").ok()
+        .map(|url| url.trim().to_string())
+        .filter(|url| !url.is_empty())
+        .unwrap_or_else(|| DEFAULT_NODE_RPC_URL.to_string())
+}
+
+/// The backend could not be used at all (unreachable, unparsable, 5xx) as opposed to answering with an error.
+/// The ifenpay API returns node errors as HTTP 500 too, so those count as answers, not failures.
+pub fn is_transport_failure(error: &ApiErrorResult) -> bool {
+    error.0.is_server_error() && !is_node_answer(error)
+}
+
+/// A node looked at the request and refused it, either from a node backend or passed through by the ifenpay API.
+fn is_node_answer(error: &ApiErrorResult) -> bool {
+    let Some(error) = error.1.0.error.as_ref() else {
+        return false;
+    };
+
+    match error.error.as_str() {
+        "NODE_RPC_REJECTED" => true,
+        "RPC_RESPONSE_ERROR" => error.message.starts_with(IFENPAY_RPC_ERROR_PREFIX),
+        _ => false,
+    }
+}
+
+/// Nodes answer exactly `Old block` for a block they already have, e.g. because another backend propagated it first.
+/// The ifenpay API passes that node error through as `RPC_RESPONSE_ERROR`. Any other rejection is not a duplicate.
+pub fn is_duplicate_publish(error: &ApiErrorResult) -> bool {
+    is_node_error(error, NODE_OLD_BLOCK)
+}
+
+/// The backend answered that the account has no blocks, as opposed to not answering at all.
+pub fn is_account_not_found(error: &ApiErrorResult) -> bool {
+    is_node_error(error, NODE_ACCOUNT_NOT_FOUND)
+}
+
+/// The backend answered that it does not have the block, as opposed to not answering at all.
+pub fn is_block_not_found(error: &ApiErrorResult) -> bool {
+    is_node_error(error, NODE_BLOCK_NOT_FOUND)
+}
+
+/// The node's error text, either from a node backend or passed through by the ifenpay API, is exactly `node_error`.
+fn is_node_error(error: &ApiErrorResult, node_error: &str) -> bool {
+    let Some(error) = error.1.0.error.as_ref() else {
+        return false;
+    };
+
+    match error.error.as_str() {
+        "NODE_RPC_REJECTED" => error.message == NodeRpcError::Rejected(node_error.to_string()).message(),
+        "RPC_RESPONSE_ERROR" => error.message == format!("{}{}", IFENPAY_RPC_ERROR_PREFIX, node_error),
+        _ => false,
+    }
+}
+
+pub fn error_text(error: &ApiErrorResult) -> String {
+    match &error.1.0.error {
+        Some(error) => format!("{}: {}", error.error, error.message),
+        None => error.0.to_string(),
+    }
+}
+
+fn unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
@@ -5,7 +5,7 @@ use std::thread;
 use serde_json::{Value, json};
 use tokio::runtime::Handle;
 
-use super::tools::{handle_tool_call, list_tools};
+use super::tools::{handle_tool_call, list_tools, tool_content_blocks};
 use crate::structs::mcp::JsonRpcRequest;
 
 pub fn initialize_mcp_stdio_server(handle: Handle) {
@@ -119,20 +119,25 @@ fn dispatch_request(handle: &Handle, request: JsonRpcRequest, id: Value) -> Valu
                 .cloned()
                 .unwrap_or_else(|| json!({}));
 
-            let call_result = handle.block_on(async move { handle_tool_call(&name, arguments).await });
+            let call_result = handle.block_on(async { handle_tool_call(&name, arguments).await });
             match call_result {
-                Ok(data) => json!({
-                    "jsonrpc": "2.0",
-                    "id": id,
-                    "result": {
-                        "content": [{
-                            "type": "text",
-                            "text": serde_json::to_string_pretty(&data).unwrap_or_else(|_| "{}".to_string())
-                        }],
-                        "structuredContent": data,
-                        "isError": false
-                    }
-                }),
+                Ok(data) => {
+                    let mut content = vec![json!({
+                        "type": "text",
+                        "text": serde_json::to_string_pretty(&data).unwrap_or_else(|_| "{}".to_string())
+                    })];
+                    content.extend(tool_content_blocks(&name, &data));
+
+                    json!({
+                        "jsonrpc": "2.0",
+                        "id": id,
+                        "result": {
+                            "content": content,
+                            "structuredContent": data,
+                            "isError": false
+                        }
+                    })
+                }
                 Err(error) => json!({
                     "jsonrpc": "2.0",
                     "id": id,
@@ -1,36 +1,190 @@
-use axum::{Json, extract::Path};
+use axum::{Json, extract::{Path, Query}};
+use base64::{Engine, engine::general_purpose::STANDARD};
 use schemars::{JsonSchema, schema_for};
 use serde_json::{Value, json};
 
 use crate::handlers::{
+    block::{build_block_api, inspect_block_api, publish_signed_block_api},
     credits::{get_credits_api, topup_credits_api},
     donate::donate_api,
+    ledger::get_ledger_status_api,
+    message::{sign_message_api, verify_message_api},
     payment::{create_payment_request_api, get_payment_status_api},
-    wallet::{get_balance_api, send_nano_api},
+    util::{address_to_public_key_api, build_uri_api, derive_addresses_api, parse_uri_api, public_key_to_address_api, validate_address_api},
+    wallets::{create_wallet_api, delete_wallet_api, import_wallet_api, list_wallets_api, lock_wallet_api, select_wallet_api},
+    wallet::{create_account_api, get_account_queue_api, get_balance_api, list_accounts_api, send_nano_api, send_uri_api, set_representative_api},
+    watch::{add_watch_only_api, get_account_history_api, get_receivable_api, list_watch_only_api, remove_watch_only_api},
 };
+use crate::helpers::qr::render_qr;
 use crate::structs::{
     api::ApiResponse,
+    block::{BuildBlockRequestApi, InspectBlockRequestApi, PublishSignedBlockRequestApi},
     credit::TopupCreditsRequestApi,
     donate::DonateRequestApi,
+    message::{SignMessageRequestApi, VerifyMessageRequestApi},
     payment::{CreatePaymentRequestApi, PaymentStatusRequestApi},
-    wallet::SendNanoRequestApi,
+    qr::QrFormat,
+    uri::{BuildUriRequestApi, ParseUriRequestApi, SendUriRequestApi},
+    util::{AddressQueryApi, DeriveAddressesRequestApi, PublicKeyQueryApi},
+    wallet::{AccountHistoryQueryApi, AddWatchOnlyRequestApi, CreateAccountRequestApi, CreateWalletRequestApi, ImportWalletRequestApi, LockWalletRequestApi, 
+        RemoveWatchOnlyRequestApi, SendNanoRequestApi, SetRepresentativeRequestApi, WalletNameRequestApi, WalletSelectorApi},
 };
 
 pub fn list_tools() -> Vec<Value> {
     vec![
         json!({
             "name": "wallet.balance",
-            "description": "Get wallet balance and pending amount.",
-            "inputSchema": empty_input_schema()
+            "description": "Get wallet balance and pending amount. Also accepts watch-only addresses and labels as account.",
+            "inputSchema": input_schema_for::<WalletSelectorApi>()
+        }),
+        json!({
+            "name": "wallet.accounts",
+            "description": "List derived wallet accounts with labels and balances.",
+            "inputSchema": input_schema_for::<WalletSelectorApi>()
+        }),
+        json!({
+            "name": "wallet.account_create",
+            "description": "Derive the next wallet account with an optional label.",
+            "inputSchema": input_schema_for::<CreateAccountRequestApi>()
+        }),
+        json!({
+            "name": "wallet.watch_list",
+            "description": "List watch-only addresses of a wallet with their balances.",
+            "inputSchema": input_schema_for::<WalletSelectorApi>()
+        }),
+        json!({
+            "name": "wallet.watch_add",
+            "description": "Add an external nano_ address as a watch-only entry (no private key, cannot spend).",
+            "inputSchema": input_schema_for::<AddWatchOnlyRequestApi>()
+        }),
+        json!({
+            "name": "wallet.watch_remove",
+            "description": "Remove a watch-only entry by address or label.",
+            "inputSchema": input_schema_for::<RemoveWatchOnlyRequestApi>()
+        }),
+        json!({
+            "name": "wallet.receivable",
+            "description": "List receivable block hashes of an owned or watch-only account without receiving them.",
+            "inputSchema": input_schema_for::<WalletSelectorApi>()
+        }),
+        json!({
+            "name": "wallet.queue",
+            "description": "Show block operations (send, receive, change, publish) waiting on an account, in the order they will be built and published.",
+            "inputSchema": input_schema_for::<WalletSelectorApi>()
+        }),
+        json!({
+            "name": "wallet.history",
+            "description": "Get the recent block history of an owned or watch-only account.",
+            "inputSchema": input_schema_for::<AccountHistoryQueryApi>()
         }),
         json!({
             "name": "wallet.send",
             "description": "Send Nano to a recipient address.",
             "inputSchema": input_schema_for::<SendNanoRequestApi>()
         }),
+        json!({
+            "name": "wallet.send_uri",
+            "description": "Pay a nano: payment URI. Amount in Nano is only needed when the URI carries none.",
+            "inputSchema": input_schema_for::<SendUriRequestApi>()
+        }),
+        json!({
+            "name": "wallet.set_representative",
+            "description": "Publish a change block that delegates an account's voting weight to a new representative.",
+            "inputSchema": input_schema_for::<SetRepresentativeRequestApi>()
+        }),
+        json!({
+            "name": "block.build",
+            "description": "Build and sign a send, receive or change block from supplied account state without network access. The exported JSON can be published later with block.publish.",
+            "inputSchema": input_schema_for::<BuildBlockRequestApi>()
+        }),
+        json!({
+            "name": "block.publish",
+            "description": "Validate and publish a pre-signed block, attaching work from the request or the work server if it has none.",
+            "inputSchema": input_schema_for::<PublishSignedBlockRequestApi>()
+        }),
+        json!({
+            "name": "block.inspect",
+            "description": "Decode a block (JSON or hash): computed hash, inferred subtype, amount, link as destination or source, signature validity and work against thresholds.",
+            "inputSchema": input_schema_for::<InspectBlockRequestApi>()
+        }),
+        json!({
+            "name": "ledger.status",
+            "description": "Show the configured ledger backends with their health scores and which backend served each recent ledger request.",
+            "inputSchema": empty_input_schema()
+        }),
+        json!({
+            "name": "message.sign",
+            "description": "Sign a UTF-8 message with a wallet account to prove ownership of its address.",
+            "inputSchema": input_schema_for::<SignMessageRequestApi>()
+        }),
+        json!({
+            "name": "message.verify",
+            "description": "Verify a signed message against any nano_ address.",
+            "inputSchema": input_schema_for::<VerifyMessageRequestApi>()
+        }),
+        json!({
+            "name": "util.validate_address",
+            "description": "Validate a nano_/xrb_ address and return its normalized form and public key, or a structured reason why it is invalid.",
+            "inputSchema": input_schema_for::<AddressQueryApi>()
+        }),
+        json!({
+            "name": "util.address_to_public_key",
+            "description": "Convert a Nano address to its 64-hex public key.",
+            "inputSchema": input_schema_for::<AddressQueryApi>()
+        }),
+        json!({
+            "name": "util.public_key_to_address",
+            "description": "Convert a 64-hex public key to its nano_ address.",
+            "inputSchema": input_schema_for::<PublicKeyQueryApi>()
+        }),
+        json!({
+            "name": "util.derive_addresses",
+            "description": "Derive addresses for a range of account indexes from a Nano seed without storing it.",
+            "inputSchema": input_schema_for::<DeriveAddressesRequestApi>()
+        }),
+        json!({
+            "name": "util.parse_uri",
+            "description": "Parse a nano: payment URI into address, amount, label and message.",
+            "inputSchema": input_schema_for::<ParseUriRequestApi>()
+        }),
+        json!({
+            "name": "util.build_uri",
+            "description": "Build a nano: payment URI from an address and optional amount, label and message.",
+            "inputSchema": input_schema_for::<BuildUriRequestApi>()
+        }),
+        json!({
+            "name": "wallets.list",
+            "description": "List registered wallets and the selected default wallet.",
+            "inputSchema": empty_input_schema()
+        }),
+        json!({
+            "name": "wallets.create",
+            "description": "Create a new named wallet with a locally generated seed.",
+            "inputSchema": input_schema_for::<CreateWalletRequestApi>()
+        }),
+        json!({
+            "name": "wallets.import",
+            "description": "Import a named wallet from a Nano seed, a private key or a BIP39 mnemonic.",
+            "inputSchema": input_schema_for::<ImportWalletRequestApi>()
+        }),
+        json!({
+            "name": "wallets.select",
+            "description": "Select the wallet used when a request does not name one.",
+            "inputSchema": input_schema_for::<WalletNameRequestApi>()
+        }),
+        json!({
+            "name": "wallets.delete",
+            "description": "Delete a named wallet that is not currently selected.",
+            "inputSchema": input_schema_for::<WalletNameRequestApi>()
+        }),
+        json!({
+            "name": "wallets.lock",
+            "description": "Lock a wallet (or every wallet) and wipe its secrets from memory. Unlocking requires the wallet password over HTTP.",
+            "inputSchema": input_schema_for::<LockWalletRequestApi>()
+        }),
         json!({
             "name": "payment.request",
-            "description": "Create a payment request for a receive address and amount.",
+            "description": "Create a payment request for a receive address and amount. Also returns the payment URI as a PNG QR code.",
             "inputSchema": input_schema_for::<CreatePaymentRequestApi>()
         }),
         json!({
@@ -41,7 +195,7 @@ pub fn list_tools() -> Vec<Value> {
         json!({
             "name": "credits.get",
             "description": "Get available credits and current credit prices.",
-            "inputSchema": empty_input_schema()
+            "inputSchema": input_schema_for::<WalletSelectorApi>()
         }),
         json!({
             "name": "credits.topup",
@@ -58,12 +212,148 @@ pub fn list_tools() -> Vec<Value> {
 
 pub async fn handle_tool_call(name: &str, arguments: Value) -> Result<Value, Value> {
     match name {
-        "wallet.balance" => map_api_result(get_balance_api().await),
+        "wallet.balance" => {
+            let selector: WalletSelectorApi = serde_json::from_value(arguments)
+                .map_err(|error| invalid_args_error(error.to_string()))?;
+            map_api_result(get_balance_api(Query(selector)).await)
+        }
+        "wallet.accounts" => {
+            let selector: WalletSelectorApi = serde_json::from_value(arguments)
+                .map_err(|error| invalid_args_error(error.to_string()))?;
+            map_api_result(list_accounts_api(Query(selector)).await)
+        }
+        "wallet.account_create" => {
+            let payload: CreateAccountRequestApi = serde_json::from_value(arguments)
+                .map_err(|error| invalid_args_error(error.to_string()))?;
+            map_api_result(create_account_api(Json(payload)).await)
+        }
+        "wallet.watch_list" => {
+            let selector: WalletSelectorApi = serde_json::from_value(arguments)
+                .map_err(|error| invalid_args_error(error.to_string()))?;
+            map_api_result(list_watch_only_api(Query(selector)).await)
+        }
+        "wallet.watch_add" => {
+            let payload: AddWatchOnlyRequestApi = serde_json::from_value(arguments)
+                .map_err(|error| invalid_args_error(error.to_string()))?;
+            map_api_result(add_watch_only_api(Json(payload)).await)
+        }
+        "wallet.watch_remove" => {
+            let payload: RemoveWatchOnlyRequestApi = serde_json::from_value(arguments)
+                .map_err(|error| invalid_args_error(error.to_string()))?;
+            map_api_result(remove_watch_only_api(Json(payload)).await)
+        }
+        "wallet.receivable" => {
+            let selector: WalletSelectorApi = serde_json::from_value(arguments)
+                .map_err(|error| invalid_args_error(error.to_string()))?;
+            map_api_result(get_receivable_api(Query(selector)).await)
+        }
+        "wallet.queue" => {
+            let selector: WalletSelectorApi = serde_json::from_value(arguments)
+                .map_err(|error| invalid_args_error(error.to_string()))?;
+            map_api_result(get_account_queue_api(Query(selector)).await)
+        }
+        "wallet.history" => {
+            let query: AccountHistoryQueryApi = serde_json::from_value(arguments)
+                .map_err(|error| invalid_args_error(error.to_string()))?;
+            map_api_result(get_account_history_api(Query(query)).await)
+        }
         "wallet.send" => {
             let payload: SendNanoRequestApi = serde_json::from_value(arguments)
                 .map_err(|error| invalid_args_error(error.to_string()))?;
             map_api_result(send_nano_api(Json(payload)).await)
         }
+        "wallet.send_uri" => {
+            let payload: SendUriRequestApi = serde_json::from_value(arguments)
+                .map_err(|error| invalid_args_error(error.to_string()))?;
+            map_api_result(send_uri_api(Json(payload)).await)
+        }
+        "wallet.set_representative" => {
+            let payload: SetRepresentativeRequestApi = serde_json::from_value(arguments)
+                .map_err(|error| invalid_args_error(error.to_string()))?;
+            map_api_result(set_representative_api(Json(payload)).await)
+        }
+        "block.build" => {
+            let payload: BuildBlockRequestApi = serde_json::from_value(arguments)
+                .map_err(|error| invalid_args_error(error.to_string()))?;
+            map_api_result(build_block_api(Json(payload)).await)
+        }
+        "block.publish" => {
+            let payload: PublishSignedBlockRequestApi = serde_json::from_value(arguments)
+                .map_err(|error| invalid_args_error(error.to_string()))?;
+            map_api_result(publish_signed_block_api(Json(payload)).await)
+        }
+        "block.inspect" => {
+            let payload: InspectBlockRequestApi = serde_json::from_value(arguments)
+                .map_err(|error| invalid_args_error(error.to_string()))?;
+            map_api_result(inspect_block_api(Json(payload)).await)
+        }
+        "ledger.status" => map_api_result(get_ledger_status_api().await),
+        "message.sign" => {
+            let payload: SignMessageRequestApi = serde_json::from_value(arguments)
+                .map_err(|error| invalid_args_error(error.to_string()))?;
+            map_api_result(sign_message_api(Json(payload)).await)
+        }
+        "message.verify" => {
+            let payload: VerifyMessageRequestApi = serde_json::from_value(arguments)
+                .map_err(|error| invalid_args_error(error.to_string()))?;
+            map_api_result(verify_message_api(Json(payload)).await)
+        }
+        "util.validate_address" => {
+            let query: AddressQueryApi = serde_json::from_value(arguments)
+                .map_err(|error| invalid_args_error(error.to_string()))?;
+            map_api_result(validate_address_api(Query(query)).await)
+        }
+        "util.address_to_public_key" => {
+            let query: AddressQueryApi = serde_json::from_value(arguments)
+                .map_err(|error| invalid_args_error(error.to_string()))?;
+            map_api_result(address_to_public_key_api(Query(query)).await)
+        }
+        "util.public_key_to_address" => {
+            let query: PublicKeyQueryApi = serde_json::from_value(arguments)
+                .map_err(|error| invalid_args_error(error.to_string()))?;
+            map_api_result(public_key_to_address_api(Query(query)).await)
+        }
+        "util.derive_addresses" => {
+            let payload: DeriveAddressesRequestApi = serde_json::from_value(arguments)
+                .map_err(|error| invalid_args_error(error.to_string()))?;
+            map_api_result(derive_addresses_api(Json(payload)).await)
+        }
+        "util.parse_uri" => {
+            let query: ParseUriRequestApi = serde_json::from_value(arguments)
+                .map_err(|error| invalid_args_error(error.to_string()))?;
+            map_api_result(parse_uri_api(Query(query)).await)
+        }
+        "util.build_uri" => {
+            let payload: BuildUriRequestApi = serde_json::from_value(arguments)
+                .map_err(|error| invalid_args_error(error.to_string()))?;
+            map_api_result(build_uri_api(Json(payload)).await)
+        }
+        "wallets.list" => map_api_result(list_wallets_api().await),
+        "wallets.create" => {
+            let payload: CreateWalletRequestApi = serde_json::from_value(arguments)
+                .map_err(|error| invalid_args_error(error.to_string()))?;
+            map_api_result(create_wallet_api(Json(payload)).await)
+        }
+        "wallets.import" => {
+            let payload: ImportWalletRequestApi = serde_json::from_value(arguments)
+                .map_err(|error| invalid_args_error(error.to_string()))?;
+            map_api_result(import_wallet_api(Json(payload)).await)
+        }
+        "wallets.select" => {
+            let payload: WalletNameRequestApi = serde_json::from_value(arguments)
+                .map_err(|error| invalid_args_error(error.to_string()))?;
+            map_api_result(select_wallet_api(Path(payload.name)).await)
+        }
+        "wallets.delete" => {
+            let payload: WalletNameRequestApi = serde_json::from_value(arguments)
+                .map_err(|error| invalid_args_error(error.to_string()))?;
+            map_api_result(delete_wallet_api(Path(payload.name)).await)
+        }
+        "wallets.lock" => {
+            let payload: LockWalletRequestApi = serde_json::from_value(arguments)
+                .map_err(|error| invalid_args_error(error.to_string()))?;
+            map_api_result(lock_wallet_api(Json(payload)).await)
+        }
         "payment.request" => {
             let payload: CreatePaymentRequestApi = serde_json::from_value(arguments)
                 .map_err(|error| invalid_args_error(error.to_string()))?;
@@ -72,18 +362,25 @@ pub async fn handle_tool_call(name: &str, arguments: Value) -> Result<Value, Val
         "payment.status" => {
             let payload: PaymentStatusRequestApi = serde_json::from_value(arguments)
                 .map_err(|error| invalid_args_error(error.to_string()))?;
-            map_api_result(get_payment_status_api(Path(payload.transaction_id)).await)
+            let selector = WalletSelectorApi { wallet: payload.wallet, account: None };
+            map_api_result(get_payment_status_api(Path(payload.transaction_id), Query(selector)).await)
+        }
+        "credits.get" => {
+            let selector: WalletSelectorApi = serde_json::from_value(arguments)
+                .map_err(|error| invalid_args_error(error.to_string()))?;
+            map_api_result(get_credits_api(Query(selector)).await)
         }
-        "credits.get" => map_api_result(get_credits_api().await),
         "credits.topup" => {
             let payload: TopupCreditsRequestApi = serde_json::from_value(arguments)
                 .map_err(|error| invalid_args_error(error.to_string()))?;
-            map_api_result(topup_credits_api(Path(payload.credits_amount)).await)
+            let selector = WalletSelectorApi { wallet: payload.wallet, account: payload.account };
+            map_api_result(topup_credits_api(Path(payload.credits_amount), Query(selector)).await)
         }
         "donate.send" => {
             let payload: DonateRequestApi = serde_json::from_value(arguments)
                 .map_err(|error| invalid_args_error(error.to_string()))?;
-            map_api_result(donate_api(Path(payload.amount)).await)
+            let selector = WalletSelectorApi { wallet: payload.wallet, account: payload.account };
+            map_api_result(donate_api(Path(payload.amount), Query(selector)).await)
         }
         _ => Err(json!({
             "error": "TOOL_NOT_FOUND",
@@ -92,6 +389,23 @@ pub async fn handle_tool_call(name: &str, arguments: Value) -> Result<Value, Val
     }
 }
 
+/// Extra MCP content blocks (besides the JSON text) for a successful tool call.
+pub fn tool_content_blocks(name: &str, data: &Value) -> Vec<Value> {
+    match name {
+        "payment.request" => data
+            .pointer("/data/payment_uri")
+            .and_then(Value::as_str)
+            .and_then(|uri| render_qr(uri, QrFormat::Png, None).ok())
+            .map(|png| vec![json!({
+                "type": "image",
+                "data": STANDARD.encode(png),
+                "mimeType": QrFormat::Png.content_type()
+            })])
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
 fn input_schema_for<T: JsonSchema>() -> Value {
     serde_json::to_value(schema_for!(T)).unwrap_or_else(|_| empty_input_schema())
 }
@@ -1,6 +1,7 @@
 pub mod app;
 pub mod routers;
 pub mod ifenpay;
+pub mod ledger;
 pub mod wallet;
 pub mod nano;
 pub mod mcp;
\ No newline at end of file
@@ -1,124 +1,242 @@
-use std::sync::Mutex;
-
 use once_cell::sync::Lazy;
 
-use crate::{enums::api::error::{block::BlockError, transaction::TransactionError, work_server::WorkServerError}, helpers::nano::{hex_to_bytes, nano_account_public_key, nano_address_to_public_key, public_key_to_nano_address, sign_hash_with_seed, state_block_hash, 
-    work_value}, services::ifenpay::api::ifenpay_api_get, structs::{api::ApiErrorResult, block::{GenerateWorkResponseApi, SignedBlock, StateBlock}, wallet::{AccountInfoResponseApi, WalletData}}};
+use crate::{enums::api::error::{block::BlockError, transaction::TransactionError, work_server::WorkServerError}, helpers::nano::{hex_to_bytes, nano_address_to_public_key, public_key_to_nano_address, sign_hash_with_private_key, state_block_hash}, 
+    services::{ledger::{chain::{reconcile_chain_state, record_published_block}, pool::{LedgerPool, is_account_not_found, ledger}}, nano::{queue::run_queued, recovery::{BlockSource, publish_with_recovery}, validation::{ZERO_HASH, validate_block, validate_unworked_block, work_threshold}}, wallet::wallet::wallet_account_private_key}, 
+    structs::{api::ApiErrorResult, block::{AccountStateApi, BlockIntent, GenerateWorkResponseApi, OfflineBlockApi, PublishAttemptApi, PublishBlockRequestApi, SignedBlock, StateBlock}, 
+    wallet::{WalletAccount, WalletData}}};
 
-static LAST_PUBLISHED_FRONTIER: Lazy<Mutex<Option<String>>> = Lazy::new(|| Mutex::new(None));
+const DEFAULT_OPEN_REPRESENTATIVE: &str = "nano_37imps4zk1dfahkqweqa91xpysacb7scqxf3jqhktepeofcxqnpx531b3mnt";
 
-const BASE_THRESH_SEND_CHANGE: u64 = 0xfffffff800000000;
-const BASE_THRESH_RECEIVE_OPEN_EPOCH: u64 = 0xfffffe0000000000;
+/// Representative for newly opened accounts: `NANO_DEFAULT_REPRESENTATIVE` if set and valid, otherwise the built-in one.
+static DEFAULT_REPRESENTATIVE_PUBLIC_KEY: Lazy<String> = Lazy::new(|| {
+    let configured = std::env::var("NANO_DEFAULT_REPRESENTATIVE").unwrap_or_default();
+    let configured = configured.trim();
+    if !configured.is_empty() {
+        match nano_address_to_public_key(configured, true) {
+            Ok(public_key) => return public_key.to_lowercase(),
+            Err(_) => eprintln!("⚠️  NANO_DEFAULT_REPRESENTATIVE is not a valid Nano address - using {}", DEFAULT_OPEN_REPRESENTATIVE),
+        }
+    }
 
-const ZERO_FRONTIER: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+    nano_address_to_public_key(DEFAULT_OPEN_REPRESENTATIVE, true)
+        .expect("DEFAULT_OPEN_REPRESENTATIVE must be a valid address")
+        .to_lowercase()
+});
 
-const DEFAULT_OPEN_REPRESENTATIVE: &str = "nano_37imps4zk1dfahkqweqa91xpysacb7scqxf3jqhktepeofcxqnpx531b3mnt";
 
+/// Builds, signs and publishes a block through the account's queue, so concurrent operations on one account
+/// never build on the same frontier. Failed publishes are recovered by `publish_with_recovery`.
+/// Returns the published block, its attempt trail and how many operations were queued ahead of it.
+pub async fn queue_account_block(
+    wallet_data: &WalletData,
+    account: &WalletAccount,
+    intent: BlockIntent<'_>,
+) -> Result<(SignedBlock, Vec<PublishAttemptApi>, usize), ApiErrorResult> {
+    let operation = match intent {
+        BlockIntent::Send { .. } => "send",
+        BlockIntent::Receive { .. } => "receive",
+        BlockIntent::Change { .. } => "change",
+    };
+
+    let (result, queue_position) = run_queued(&account.address, operation, || async {
+        let signed_block = prepare_generate_and_sign_block(wallet_data, account, intent).await?;
+        publish_with_recovery(ledger(), signed_block, BlockSource::Wallet { wallet_data, account, intent }, &wallet_data.api_key).await
+    }).await;
+
+    result.map(|(signed_block, attempts)| (signed_block, attempts, queue_position))
+}
 
+/// Not serialized: callers that publish the block must hold the account's turn in `run_queued`.
 pub async fn prepare_generate_and_sign_block(
     wallet_data: &WalletData,
-    amount_raw: u128,
-    link: &str,
-    is_receive: bool,
+    account: &WalletAccount,
+    intent: BlockIntent<'_>,
 ) -> Result<SignedBlock, ApiErrorResult> {
-    let api_key = wallet_data.api_key.clone();
+    let state = current_account_state(ledger(), account, &wallet_data.api_key).await?;
 
-    let mut frontier = ZERO_FRONTIER.to_string();
-    let mut balance = "0".to_string();
-    let mut representative = wallet_data.public_key.clone();
+    build_account_block(wallet_data, account, &state, intent, None).await
+}
 
-    if let Ok(account_info) = wait_for_latest_account_info(&wallet_data.address, &api_key).await {
-        frontier = account_info.frontier;
-        balance = account_info.balance;
-        representative = account_info.representative;
-    }
+/// The state to build the account's next block on, from the backend reconciled with the chain state cache.
+/// Only an account the backend does not know is treated as unopened; any other backend error is returned, so an
+/// outage cannot make an open block over an existing chain.
+pub async fn current_account_state(ledger: &LedgerPool, account: &WalletAccount, api_key: &str) -> Result<AccountStateApi, ApiErrorResult> {
+    let account_info = match ledger.account_info(&account.address, api_key).await {
+        Ok(account_info) => Some(account_info),
+        Err(error) if is_account_not_found(&error) => None,
+        Err(error) => return Err(error),
+    };
 
-    let rep_public_key = resolve_representative_public_key(&frontier, &representative, &wallet_data.public_key);
+    Ok(match reconcile_chain_state(&account.address, account_info.as_ref()) {
+        Some(chain_state) => chain_state.account_state(),
+        None => unopened_account_state(account),
+    })
+}
 
-    let current_balance_u = balance.parse::<u128>().unwrap_or(0);
-    if !is_receive && current_balance_u < amount_raw {
-        return Err(TransactionError::InsufficientFunds.to_response());
-    }
+/// Signs a block on top of `state` and attaches work. The work of `replaced` is reused when the block it was
+/// computed for has the same root, i.e. only the signed contents changed.
+pub async fn build_account_block(
+    wallet_data: &WalletData,
+    account: &WalletAccount,
+    state: &AccountStateApi,
+    intent: BlockIntent<'_>,
+    replaced: Option<&SignedBlock>,
+) -> Result<SignedBlock, ApiErrorResult> {
+    let offline_block = sign_block_offline(wallet_data, account, state, intent)?;
+    let work = replaced
+        .filter(|replaced| replaced.work_root.eq_ignore_ascii_case(&offline_block.work_root) && replaced.subtype == offline_block.subtype)
+        .map(|replaced| replaced.block.work.clone());
 
-    let subtype = if is_receive {
-        if frontier == ZERO_FRONTIER { "open" } else { "receive" }
-    } else {
-        "send"
-    };
+    complete_signed_block(offline_block, work).await
+}
 
-    let threshold = if is_receive {
-        if frontier == ZERO_FRONTIER {
-            BASE_THRESH_RECEIVE_OPEN_EPOCH
-        } else {
-            BASE_THRESH_RECEIVE_OPEN_EPOCH 
-        }
-    } else {
-        BASE_THRESH_SEND_CHANGE
-    };
+pub fn unopened_account_state(account: &WalletAccount) -> AccountStateApi {
+    AccountStateApi {
+        frontier: ZERO_HASH.to_string(),
+        balance: "0".to_string(),
+        representative: account.public_key.clone(),
+    }
+}
 
-    let seed_hex = &wallet_data.wallet_private_seed.clone();
-    let new_balance_u = if is_receive {
-        current_balance_u + amount_raw
-    } else {
-        current_balance_u - amount_raw
-    };
-    let work_root = if subtype == "open" {
-        wallet_data.public_key.clone()
-    } else {
-        frontier.clone()
+/// Builds and signs a block on top of `state` without any network access. The block has no work yet.
+pub fn sign_block_offline(
+    wallet_data: &WalletData,
+    account: &WalletAccount,
+    state: &AccountStateApi,
+    intent: BlockIntent<'_>,
+) -> Result<OfflineBlockApi, ApiErrorResult> {
+    let frontier = state.frontier.trim().to_uppercase();
+    let current_rep_public_key = resolve_representative_public_key(&frontier, &state.representative, &account.public_key);
+    let current_balance_u = state.balance.trim().parse::<u128>()
+        .map_err(|_| BlockError::InvalidBalance.to_response())?;
+
+    let (subtype, new_balance_u, link, rep_public_key) = match intent {
+        BlockIntent::Send { amount_raw, link } => {
+            if current_balance_u < amount_raw {
+                return Err(TransactionError::InsufficientFunds.to_response());
+            }
+            ("send", current_balance_u - amount_raw, link.to_string(), current_rep_public_key)
+        }
+        BlockIntent::Receive { amount_raw, link } => {
+            let subtype = if frontier == ZERO_HASH { "open" } else { "receive" };
+            let new_balance_u = current_balance_u.checked_add(amount_raw)
+                .ok_or_else(|| TransactionError::AmountToLarge.to_response())?;
+            (subtype, new_balance_u, link.to_string(), current_rep_public_key)
+        }
+        BlockIntent::Change { representative_public_key } => {
+            if frontier == ZERO_HASH {
+                return Err(BlockError::UnopenedAccount.to_response());
+            }
+            let representative_public_key = representative_public_key.to_lowercase();
+            if representative_public_key == current_rep_public_key {
+                return Err(BlockError::RepresentativeUnchanged.to_response());
+            }
+            ("change", current_balance_u, ZERO_HASH.to_string(), representative_public_key)
+        }
     };
 
-
-    let account_pub32 = nano_account_public_key(seed_hex, 0)
+    let private_key = wallet_account_private_key(wallet_data, account.index)
+        .map_err(|_| BlockError::KeyDerivationFailed.to_response())?;
+    let account_pub32 = hex_to_bytes(&account.public_key)
         .map_err(|_| BlockError::KeyDerivationFailed.to_response())?;
     let prev_32  = hex_to_bytes(&frontier)
         .map_err(|_| BlockError::InvalidPreviousHash.to_response())?;
     let rep_pub32  = hex_to_bytes(&rep_public_key)
         .map_err(|_| BlockError::InvalidRepresentativePublicKey.to_response())?;
-    let link_32 = hex_to_bytes(link)
+    let link_32 = hex_to_bytes(&link)
         .map_err(|_| BlockError::InvalidLink.to_response())?;
     let h32 = state_block_hash(&account_pub32, &prev_32, &rep_pub32, new_balance_u, &link_32)
         .map_err(|_| BlockError::BlockHashGenerationFailed.to_response())?;
-    let sig_hex = sign_hash_with_seed(&seed_hex, 0, &h32)
+    let sig_hex = sign_hash_with_private_key(&private_key, &h32)
         .map_err(|_| BlockError::SigningFailed.to_response())?;
-    let work_hex = generate_work(&work_root, &threshold).await?;
 
     let block = StateBlock {
         block_type: "state".to_string(),
-        account: wallet_data.address.clone(),
-        previous: frontier.clone(),
+        account: account.address.clone(),
+        previous: frontier,
         representative: public_key_to_nano_address(&rep_public_key)
             .map_err(|_| BlockError::InvalidRepresentativeAddress.to_response())?,
         balance: new_balance_u.to_string(),
-        link: link.to_string(),
+        link,
         signature: sig_hex,
-        work: work_hex.work.clone(),
+        work: String::new(),
     };
-    
-    let mut work_le = hex_to_bytes(&work_hex.work)
-        .map_err(|_| BlockError::InvalidWorkHex.to_response())?;
-    work_le.reverse();
-    let work_le: [u8; 8] = work_le.try_into()
-        .map_err(|_| BlockError::InvalidWorkLength.to_response())?;
 
-    let work_root_bytes: [u8; 32] = hex_to_bytes(&work_root)
-        .map_err(|_| BlockError::InvalidWorkRoot.to_response())?
-        .try_into()
-        .map_err(|_| BlockError::InvalidWorkRootLength.to_response())?;
+    offline_block(block, subtype, current_balance_u)
+}
+
+/// Wraps an already signed block after checking everything but its work.
+pub fn offline_block(block: StateBlock, subtype: &str, previous_balance: u128) -> Result<OfflineBlockApi, ApiErrorResult> {
+    let validated = validate_unworked_block(&block, subtype, previous_balance)?;
+
+    Ok(OfflineBlockApi {
+        subtype: subtype.to_string(),
+        block,
+        hash: validated.hash,
+        previous_balance: previous_balance.to_string(),
+        work_root: validated.work_root,
+        threshold: format!("{:016x}", validated.threshold),
+    })
+}
+
+/// Attaches `work` to an offline block and validates the result, without network access.
+pub fn attach_work(offline_block: OfflineBlockApi, work: &str) -> Result<SignedBlock, ApiErrorResult> {
+    let previous_balance = offline_block.previous_balance.parse::<u128>()
+        .map_err(|_| BlockError::InvalidBalance.to_response())?;
+    let mut block = offline_block.block;
+    block.work = work.trim().to_lowercase();
 
-    let work_val = work_value(&work_root_bytes, &work_le)
-        .map_err(|_| BlockError::CalculateWorkFailed.to_response())?;
+    let validated = validate_block(&block, &offline_block.subtype, previous_balance)?;
 
     Ok(SignedBlock {
         block,
-        hash: hex::encode(h32),
-        account_public_key: wallet_data.public_key.clone(),
-        subtype: subtype.to_string(),
-        work_root: work_root.clone(),
-        threshold_hex: format!("0x{:016x}", threshold),
-        work_value_hex: format!("0x{:016x}", work_val),
+        hash: validated.hash,
+        account_public_key: validated.account_public_key,
+        subtype: offline_block.subtype,
+        work_root: validated.work_root,
+        threshold_hex: format!("0x{:016x}", validated.threshold),
+        work_value_hex: format!("0x{:016x}", validated.work_value),
     })
+}
+
+/// Attaches `work`, or work from the work server when `None`, and validates the block.
+pub async fn complete_signed_block(offline_block: OfflineBlockApi, work: Option<String>) -> Result<SignedBlock, ApiErrorResult> {
+    let work = match work {
+        Some(work) => work,
+        None => {
+            let threshold = work_threshold(&offline_block.subtype)
+                .ok_or_else(|| BlockError::InvalidSubtype.to_response())?;
+            generate_work(&offline_block.work_root, &threshold).await?.work
+        }
+    };
+
+    attach_work(offline_block, &work)
+}
+
+pub async fn publish_block(ledger: &'static LedgerPool, signed_block: &SignedBlock, api_key: &str) -> Result<(), ApiErrorResult> {
+    let request_payload = PublishBlockRequestApi {
+        subtype: signed_block.subtype.clone(),
+        block: signed_block.block.clone(),
+    };
+    ledger.publish(&request_payload, &signed_block.hash, api_key).await?;
+    record_published_block(signed_block);
 
+    Ok(())
+}
+
+#[cfg(test)]
+tokio::task_local! {
+    /// Work server used inside `scope` instead of `NANO_WORK_SERVER_URL`, so tests do not touch the process environment.
+    pub static TEST_WORK_SERVER_URL: String;
+}
+
+fn work_server_url() -> String {
+    #[cfg(test)]
+    if let Ok(url) = TEST_WORK_SERVER_URL.try_with(String::clone) {
+        return url;
+    }
+
+    std::env::var("NANO_WORK_SERVER_URL").unwrap_or_else(|_| "http://127.0.0.1:4000".to_string())
 }
 
 async fn generate_work(
@@ -127,19 +245,18 @@ async fn generate_work(
 ) -> Result<GenerateWorkResponseApi, ApiErrorResult> {
     let client = reqwest::Client::new();
     let threshold_hex = format!("{:016x}", threshold);
-    let work_server_url = std::env::var("NANO_WORK_SERVER_URL").unwrap_or_else(|_| "http://127.0.0.1:4000".to_string());
-    let response_text = client.post(&work_server_url)
-    .json(&serde_json::json!({
-        "action": "work_generate",
-        "hash": hash,
-        "difficulty": threshold_hex
-    }))
-    .send()
-    .await
-    .unwrap()
-    .text()
-    .await
-    .unwrap();
+    let response_text = client.post(work_server_url())
+        .json(&serde_json::json!({
+            "action": "work_generate",
+            "hash": hash,
+            "difficulty": threshold_hex
+        }))
+        .send()
+        .await
+        .map_err(|error| WorkServerError::Unreachable(error.to_string()).to_response())?
+        .text()
+        .await
+        .map_err(|error| WorkServerError::Error(format!("Failed to read response: {}", error)).to_response())?;
 
     if let Ok(work_response) = serde_json::from_str::<GenerateWorkResponseApi>(&response_text) {
         return Ok(work_response);
@@ -148,52 +265,9 @@ async fn generate_work(
     }
 }
 
-async fn wait_for_latest_account_info(address: &str, api_key: &str) -> Result<AccountInfoResponseApi, ()> {
-    let mut account_info_response = ifenpay_api_get::<AccountInfoResponseApi>(&format!("/account/info/{}", address), Some(api_key)).await;
-    let mut loop_attempts = 0;
-
-    loop {
-        if let Ok(account_info) = &account_info_response {
-            let account_info = match &account_info.data {
-                Some(data) => data,
-                None => {
-                    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
-                    account_info_response = ifenpay_api_get::<AccountInfoResponseApi>(&format!("/account/info/{}", address), Some(api_key)).await;
-                    loop_attempts += 1;
-                    if loop_attempts >= 10 {
-                        break;
-                    }
-                    continue;
-                }
-            };
-
-            if LAST_PUBLISHED_FRONTIER.lock().unwrap().as_deref() != Some(&account_info.frontier) {
-                *LAST_PUBLISHED_FRONTIER.lock().unwrap() = Some(account_info.frontier.clone());
-                return Ok(account_info.clone());
-            }
-        }
-
-        tokio::time::sleep(std::time::Duration::from_millis(500)).await;
-        account_info_response = ifenpay_api_get::<AccountInfoResponseApi>(&format!("/account/info/{}", address), Some(api_key)).await;
-
-        loop_attempts += 1;
-        if loop_attempts >= 10 {
-            break;
-        }
-    }
-
-
-    if account_info_response.is_err() {
-        return Err(());
-    }
-
-    account_info_response.unwrap().data.ok_or(()).map_err(|_| ())
-}
-
 fn resolve_representative_public_key(frontier: &str, representative: &str, public_key: &str) -> String {
-    if frontier == ZERO_FRONTIER {
-        return nano_address_to_public_key(DEFAULT_OPEN_REPRESENTATIVE, true)
-            .unwrap_or_else(|_| public_key.to_lowercase());
+    if frontier == ZERO_HASH {
+        return DEFAULT_REPRESENTATIVE_PUBLIC_KEY.clone();
     }
 
     if !representative.is_empty() {
@@ -0,0 +1,162 @@
+use crate::{enums::api::error::block::BlockError, helpers::{block::{encode_state_block, legacy_block_hash}, nano::{hex_to_bytes, public_key_to_nano_address, raw_to_nano, state_block_hash, verify_hash_signature, work_value}}, 
+    services::nano::validation::{BASE_THRESH_RECEIVE_OPEN_EPOCH, BASE_THRESH_SEND_CHANGE, ZERO_HASH, address_public_key_bytes, validate_block}, 
+    structs::{api::{ApiError, ApiErrorResult}, block::{BlockInspectionApi, LegacyBlock, StateBlock}}};
+
+/// `link` of epoch upgrade blocks: "epoch v1 block" / "epoch v2 block" padded with zeros.
+const EPOCH_LINKS: [&str; 2] = [
+    "65706F636820763120626C6F636B000000000000000000000000000000000000",
+    "65706F636820763220626C6F636B000000000000000000000000000000000000",
+];
+
+/// Decodes a state block without rejecting it. Only fields needed for the hash must be well-formed;
+/// signature and work problems are reported in the result.
+pub fn inspect_block(block: &StateBlock, previous_balance: Option<u128>) -> Result<BlockInspectionApi, ApiErrorResult> {
+    let account_pub32 = address_public_key_bytes(&block.account)
+        .ok_or_else(|| BlockError::InvalidAccountAddress.to_response())?;
+    let prev_32 = hex_to_bytes(&block.previous).ok().filter(|bytes| bytes.len() == 32)
+        .ok_or_else(|| BlockError::InvalidPreviousHash.to_response())?;
+    let rep_pub32 = address_public_key_bytes(&block.representative)
+        .ok_or_else(|| BlockError::InvalidRepresentativeAddress.to_response())?;
+    let link_32 = hex_to_bytes(&block.link).ok().filter(|bytes| bytes.len() == 32)
+        .ok_or_else(|| BlockError::InvalidLink.to_response())?;
+    let balance = block.balance.trim().parse::<u128>()
+        .map_err(|_| BlockError::InvalidBalance.to_response())?;
+    let h32 = state_block_hash(&account_pub32, &prev_32, &rep_pub32, balance, &link_32)
+        .map_err(|_| BlockError::BlockHashGenerationFailed.to_response())?;
+
+    let is_open = prev_32.iter().all(|byte| *byte == 0);
+    let previous_balance = if is_open { Some(0) } else { previous_balance };
+    let link = hex::encode_upper(&link_32);
+    let subtype = infer_subtype(is_open, &link, balance, previous_balance);
+
+    let amount_raw = previous_balance.map(|previous_balance| balance.abs_diff(previous_balance));
+    let destination = matches!(subtype, Some("send") | None)
+        .then(|| public_key_to_nano_address(&link).ok())
+        .flatten();
+    let source = matches!(subtype, Some("receive") | Some("open") | None).then(|| link.clone());
+
+    let signature_valid = hex_to_bytes(&block.signature).ok()
+        .filter(|signature| signature.len() == 64)
+        .and_then(|signature| verify_hash_signature(&account_pub32, &h32, &signature).ok())
+        .unwrap_or(false);
+
+    let work_root = if is_open { &account_pub32 } else { &prev_32 };
+    let work_val = hex_to_bytes(&block.work).ok()
+        .and_then(|mut work| {
+            work.reverse();
+            work_value(work_root, &work).ok()
+        })
+        .filter(|_| block.work.trim().len() == 16);
+
+    let validation = match (subtype, previous_balance) {
+        (Some(subtype), Some(previous_balance)) if subtype != "epoch" => Some(validate_block(block, subtype, previous_balance)),
+        _ => None,
+    };
+
+    Ok(BlockInspectionApi {
+        hash: hex::encode(&h32),
+        account: block.account.clone(),
+        subtype: subtype.map(str::to_string),
+        previous_balance: previous_balance.map(|balance| balance.to_string()),
+        balance: balance.to_string(),
+        amount_raw: amount_raw.map(|amount| amount.to_string()),
+        amount: amount_raw.map(raw_to_nano),
+        link,
+        destination,
+        source,
+        signature_valid,
+        work_root: hex::encode_upper(work_root),
+        work_value: work_val.map(|value| format!("{:016x}", value)),
+        meets_send_threshold: work_val.is_some_and(|value| value >= BASE_THRESH_SEND_CHANGE),
+        meets_receive_threshold: work_val.is_some_and(|value| value >= BASE_THRESH_RECEIVE_OPEN_EPOCH),
+        publishable: matches!(validation, Some(Ok(_))),
+        validation_error: match validation {
+            Some(Err((_, response))) => response.0.error,
+            _ => None,
+        },
+        binary: encode_state_block(block).ok().map(hex::encode_upper),
+    })
+}
+
+/// Decodes a legacy block. Legacy blocks carry neither the account (except `open`) nor, apart from `send`, the balance,
+/// so both come from the ledger entry. They can no longer be published, which is reported as the validation error.
+pub fn inspect_legacy_block(block: &LegacyBlock, account: &str, balance: u128, amount_raw: Option<u128>) -> Result<BlockInspectionApi, ApiErrorResult> {
+    let account_pub32 = address_public_key_bytes(account)
+        .ok_or_else(|| BlockError::InvalidAccountAddress.to_response())?;
+    let h32 = legacy_block_hash(block)?;
+
+    let (work_root, link, destination, source) = match block {
+        LegacyBlock::Send { previous, destination, .. } => {
+            let destination_pub32 = address_public_key_bytes(destination)
+                .ok_or_else(|| BlockError::InvalidAccountAddress.to_response())?;
+            (previous.to_uppercase(), hex::encode_upper(destination_pub32), Some(destination.clone()), None)
+        }
+        LegacyBlock::Receive { previous, source, .. } => (previous.to_uppercase(), source.to_uppercase(), None, Some(source.to_uppercase())),
+        LegacyBlock::Open { source, .. } => (hex::encode_upper(&account_pub32), source.to_uppercase(), None, Some(source.to_uppercase())),
+        LegacyBlock::Change { previous, .. } => (previous.to_uppercase(), ZERO_HASH.to_string(), None, None),
+    };
+
+    let amount_raw = match block {
+        LegacyBlock::Change { .. } => Some(0),
+        _ => amount_raw,
+    };
+    let previous_balance = match block {
+        LegacyBlock::Send { .. } => amount_raw.and_then(|amount| balance.checked_add(amount)),
+        LegacyBlock::Receive { .. } => amount_raw.and_then(|amount| balance.checked_sub(amount)),
+        LegacyBlock::Open { .. } => Some(0),
+        LegacyBlock::Change { .. } => Some(balance),
+    };
+
+    let signature_valid = hex_to_bytes(block.signature()).ok()
+        .filter(|signature| signature.len() == 64)
+        .and_then(|signature| verify_hash_signature(&account_pub32, &h32, &signature).ok())
+        .unwrap_or(false);
+
+    let work_val = hex_to_bytes(&work_root).ok()
+        .zip(hex_to_bytes(block.work()).ok())
+        .and_then(|(root, mut work)| {
+            work.reverse();
+            work_value(&root, &work).ok()
+        })
+        .filter(|_| block.work().trim().len() == 16);
+
+    Ok(BlockInspectionApi {
+        hash: hex::encode(&h32),
+        account: account.to_string(),
+        subtype: Some(block.block_type().to_string()),
+        previous_balance: previous_balance.map(|balance| balance.to_string()),
+        balance: balance.to_string(),
+        amount_raw: amount_raw.map(|amount| amount.to_string()),
+        amount: amount_raw.map(raw_to_nano),
+        link,
+        destination,
+        source,
+        signature_valid,
+        work_root,
+        work_value: work_val.map(|value| format!("{:016x}", value)),
+        meets_send_threshold: work_val.is_some_and(|value| value >= BASE_THRESH_SEND_CHANGE),
+        meets_receive_threshold: work_val.is_some_and(|value| value >= BASE_THRESH_RECEIVE_OPEN_EPOCH),
+        publishable: false,
+        validation_error: Some(ApiError {
+            error: BlockError::InvalidBlockType.error().to_string(),
+            message: BlockError::InvalidBlockType.message(),
+        }),
+        binary: None,
+    })
+}
+
+fn infer_subtype(is_open: bool, link: &str, balance: u128, previous_balance: Option<u128>) -> Option<&'static str> {
+    if is_open {
+        return Some("open");
+    }
+
+    let zero_link = link.bytes().all(|byte| byte == b'0');
+    let epoch_link = EPOCH_LINKS.contains(&link);
+    match previous_balance {
+        Some(previous_balance) if balance < previous_balance => Some("send"),
+        Some(previous_balance) if balance > previous_balance => Some("receive"),
+        _ if zero_link => Some("change"),
+        _ if epoch_link => Some("epoch"),
+        _ => None,
+    }
+}
@@ -1 +1,5 @@
-pub mod block;
\ No newline at end of file
+pub mod block;
+pub mod validation;
+pub mod inspect;
+pub mod queue;
+pub mod recovery;
\ No newline at end of file
@@ -0,0 +1,115 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use once_cell::sync::Lazy;
+use tokio::sync::watch;
+
+use crate::structs::wallet::{AccountQueueApi, QueuedOperationApi};
+
+/// Queues of accounts with operations running or waiting; an account's queue is removed once it is empty.
+/// Locked before any queue's `entries`.
+static ACCOUNT_QUEUES: Lazy<Mutex<HashMap<String, Arc<AccountQueue>>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+static NEXT_TICKET: AtomicU64 = AtomicU64::new(1);
+
+/// Operations waiting on one account. The front entry runs; `changed` wakes the others whenever an entry leaves.
+struct AccountQueue {
+    entries: Mutex<VecDeque<QueuedOperationApi>>,
+    changed: watch::Sender<()>,
+}
+
+/// Removes its entry when the operation finishes or the caller gives up waiting, so the next one can run.
+/// The last entry to leave also removes the account's queue.
+struct QueueEntry {
+    address: String,
+    queue: Arc<AccountQueue>,
+    ticket: u64,
+}
+
+impl Drop for QueueEntry {
+    fn drop(&mut self) {
+        {
+            let mut queues = ACCOUNT_QUEUES.lock().unwrap();
+            let mut entries = self.queue.entries.lock().unwrap();
+            entries.retain(|entry| entry.ticket != self.ticket);
+            if entries.is_empty() && queues.get(&self.address).is_some_and(|queue| Arc::ptr_eq(queue, &self.queue)) {
+                queues.remove(&self.address);
+            }
+        }
+        self.queue.changed.send_replace(());
+    }
+}
+
+/// Runs `run` once every operation queued earlier on `address` has finished, so blocks on one account chain are
+/// built and published strictly one after another. Also returns how many operations were ahead in the queue.
+pub async fn run_queued<T, F, Fut>(address: &str, operation: &str, run: F) -> (T, usize)
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = T>,
+{
+    // Joining happens under the map lock, so a queue is never removed between being looked up and being joined.
+    let (queue, ticket, position) = {
+        let mut queues = ACCOUNT_QUEUES.lock().unwrap();
+        let queue = queues.entry(address.to_string())
+            .or_insert_with(|| Arc::new(AccountQueue {
+                entries: Mutex::new(VecDeque::new()),
+                changed: watch::channel(()).0,
+            }))
+            .clone();
+
+        let mut entries = queue.entries.lock().unwrap();
+        let ticket = NEXT_TICKET.fetch_add(1, Ordering::Relaxed);
+        entries.push_back(QueuedOperationApi {
+            ticket,
+            operation: operation.to_string(),
+            enqueued_at: unix_timestamp(),
+            running: false,
+        });
+        let position = entries.len() - 1;
+        drop(entries);
+
+        (queue, ticket, position)
+    };
+    let _entry = QueueEntry { address: address.to_string(), queue: queue.clone(), ticket };
+    let mut changed = queue.changed.subscribe();
+
+    loop {
+        {
+            let mut entries = queue.entries.lock().unwrap();
+            if let Some(front) = entries.front_mut()
+                && front.ticket == ticket {
+                front.running = true;
+                break;
+            }
+        }
+        let _ = changed.changed().await;
+    }
+
+    (run().await, position)
+}
+
+pub fn account_queue(address: &str) -> AccountQueueApi {
+    let operations: Vec<QueuedOperationApi> = ACCOUNT_QUEUES.lock().unwrap()
+        .get(address)
+        .map(|queue| queue.entries.lock().unwrap().iter().cloned().collect())
+        .unwrap_or_default();
+
+    AccountQueueApi {
+        account: address.to_string(),
+        depth: operations.len(),
+        operations,
+    }
+}
+
+#[cfg(test)]
+pub fn has_account_queue(address: &str) -> bool {
+    ACCOUNT_QUEUES.lock().unwrap().contains_key(address)
+}
+
+fn unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
@@ -0,0 +1,244 @@
+use std::time::Duration;
+
+use crate::{enums::api::error::publish::PublishError,
+    services::{ledger::{chain::{record_published_block, reset_chain_state}, pool::{LedgerPool, error_text, is_account_not_found, is_block_not_found, is_duplicate_publish, is_transport_failure}},
+    nano::block::{build_account_block, complete_signed_block, current_account_state, offline_block, publish_block, unopened_account_state}},
+    structs::{api::ApiErrorResult, block::{AccountStateApi, BlockIntent, PublishAttemptApi, SignedBlock, StateBlock}, wallet::{WalletAccount, WalletData}}};
+
+/// Publishes per block, the first one included.
+const MAX_PUBLISH_ATTEMPTS: u32 = 3;
+/// Waited before attempt n + 1, multiplied by n.
+const PUBLISH_RETRY_DELAY_MS: u64 = 250;
+
+/// Why the ledger did not take a block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PublishFailure {
+    /// Another block with the same previous is already in the ledger.
+    Fork,
+    /// The block's previous block is unknown to the ledger.
+    GapPrevious,
+    /// The ledger already has this very block.
+    OldBlock,
+    InsufficientWork,
+    /// No backend could be reached.
+    Unreachable,
+    /// The work server failed while a block was being repaired.
+    WorkUnavailable,
+    /// Anything else, e.g. a bad signature or an unreceivable source. Retrying does not help.
+    Rejected,
+}
+
+impl PublishFailure {
+    /// Nodes report the reason only as text (`Fork`, `Gap previous block`, `Block work is less than threshold`, ...).
+    pub fn classify(error: &ApiErrorResult) -> PublishFailure {
+        let (code, message) = error.1.0.error.as_ref()
+            .map(|error| (error.error.as_str(), error.message.to_lowercase()))
+            .unwrap_or_default();
+
+        if is_duplicate_publish(error) {
+            PublishFailure::OldBlock
+        } else if message.contains("fork") {
+            PublishFailure::Fork
+        } else if message.contains("gap previous") {
+            PublishFailure::GapPrevious
+        } else if code == "INSUFFICIENT_WORK" || (message.contains("work") && (message.contains("insufficient") || message.contains("threshold"))) {
+            PublishFailure::InsufficientWork
+        } else if code.starts_with("WORK_SERVER_") {
+            PublishFailure::WorkUnavailable
+        } else if is_transport_failure(error) {
+            PublishFailure::Unreachable
+        } else {
+            PublishFailure::Rejected
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PublishFailure::Fork => "fork",
+            PublishFailure::GapPrevious => "gap_previous",
+            PublishFailure::OldBlock => "old_block",
+            PublishFailure::InsufficientWork => "insufficient_work",
+            PublishFailure::Unreachable => "unreachable",
+            PublishFailure::WorkUnavailable => "work_unavailable",
+            PublishFailure::Rejected => "rejected",
+        }
+    }
+
+    fn to_error(self, attempts: &[PublishAttemptApi]) -> ApiErrorResult {
+        let trail = attempt_trail(attempts);
+        match self {
+            PublishFailure::Fork => PublishError::Fork(trail),
+            PublishFailure::GapPrevious => PublishError::GapPrevious(trail),
+            PublishFailure::InsufficientWork => PublishError::InsufficientWork(trail),
+            PublishFailure::Unreachable => PublishError::Unreachable(trail),
+            PublishFailure::WorkUnavailable => PublishError::WorkUnavailable(trail),
+            PublishFailure::OldBlock | PublishFailure::Rejected => PublishError::Rejected(trail),
+        }.to_response()
+    }
+}
+
+/// Where a block came from, which decides how far it can be repaired.
+#[derive(Clone, Copy)]
+pub enum BlockSource<'a> {
+    /// Built for a wallet account, so it can be signed again on a new frontier.
+    Wallet { wallet_data: &'a WalletData, account: &'a WalletAccount, intent: BlockIntent<'a> },
+    /// Signed elsewhere; only its work can be replaced.
+    Presigned { previous_balance: u128 },
+}
+
+/// Publishes a block and recovers from what the ledger reports: a fork re-signs the block on the account's current
+/// frontier, an unknown previous block waits for it (or rebuilds on the backend's frontier when the ledger does not
+/// know it at all), insufficient work is regenerated and unreachable backends are retried. Gives up after
+/// `MAX_PUBLISH_ATTEMPTS`; the error then carries the attempt trail. Callers must hold the account's turn in `run_queued`.
+pub async fn publish_with_recovery(
+    ledger: &'static LedgerPool,
+    mut signed_block: SignedBlock,
+    source: BlockSource<'_>,
+    api_key: &str,
+) -> Result<(SignedBlock, Vec<PublishAttemptApi>), ApiErrorResult> {
+    let mut attempts: Vec<PublishAttemptApi> = Vec::new();
+
+    loop {
+        let attempt = attempts.len() as u32 + 1;
+        let error = match publish_block(ledger, &signed_block, api_key).await {
+            Ok(()) => {
+                attempts.push(publish_attempt(attempt, &signed_block, "published", None));
+                return Ok((signed_block, attempts));
+            }
+            Err(error) => error,
+        };
+
+        let failure = PublishFailure::classify(&error);
+        attempts.push(publish_attempt(attempt, &signed_block, failure.as_str(), Some(error_text(&error))));
+        if failure == PublishFailure::OldBlock {
+            record_published_block(&signed_block);
+            return Ok((signed_block, attempts));
+        }
+        if attempt >= MAX_PUBLISH_ATTEMPTS {
+            return Err(failure.to_error(&attempts));
+        }
+
+        tokio::time::sleep(Duration::from_millis(PUBLISH_RETRY_DELAY_MS * attempt as u64)).await;
+        let recovered = recover(ledger, failure, &signed_block, source, api_key).await;
+        let last_attempt = attempts.last_mut().expect("attempt was just recorded");
+        match recovered {
+            Ok(Some((next_block, recovery))) => {
+                last_attempt.recovery = Some(recovery.to_string());
+                signed_block = next_block;
+            }
+            Ok(None) => return Err(failure.to_error(&attempts)),
+            Err(error) => {
+                // A work server outage while repairing is reported as such, not as the original rejection.
+                last_attempt.recovery = Some(format!("failed: {}", error_text(&error)));
+                let failure = match PublishFailure::classify(&error) {
+                    PublishFailure::WorkUnavailable => PublishFailure::WorkUnavailable,
+                    _ => failure,
+                };
+                return Err(failure.to_error(&attempts));
+            }
+        }
+    }
+}
+
+/// The block to try next and what was done to get it, or `None` when this failure cannot be fixed by retrying.
+async fn recover(
+    ledger: &LedgerPool,
+    failure: PublishFailure,
+    signed_block: &SignedBlock,
+    source: BlockSource<'_>,
+    api_key: &str,
+) -> Result<Option<(SignedBlock, &'static str)>, ApiErrorResult> {
+    match (failure, source) {
+        (PublishFailure::Unreachable, _) => Ok(Some((signed_block.clone(), "retried"))),
+        (PublishFailure::GapPrevious, BlockSource::Presigned { .. }) => Ok(Some((signed_block.clone(), "waited_for_previous"))),
+        (PublishFailure::InsufficientWork, BlockSource::Presigned { previous_balance }) => {
+            let block = StateBlock { work: String::new(), ..signed_block.block.clone() };
+            let offline = offline_block(block, &signed_block.subtype, previous_balance)?;
+            Ok(Some((complete_signed_block(offline, None).await?, "regenerated_work")))
+        }
+        (PublishFailure::InsufficientWork, BlockSource::Wallet { wallet_data, account, intent }) => {
+            let state = current_account_state(ledger, account, api_key).await?;
+            rebuild(wallet_data, account, &state, intent, signed_block, false).await.map(Some)
+        }
+        (PublishFailure::GapPrevious, BlockSource::Wallet { wallet_data, account, intent }) => {
+            // A previous block the ledger knows has just not reached the publishing backend yet. One it does not know
+            // never landed, so the cached frontier is dropped for the backend's view.
+            match ledger.block_info(&signed_block.block.previous, api_key).await {
+                Ok(_) => Ok(Some((signed_block.clone(), "waited_for_previous"))),
+                Err(error) if is_block_not_found(&error) => {
+                    let state = reset_account_state(ledger, account, api_key).await?;
+                    rebuild(wallet_data, account, &state, intent, signed_block, true).await.map(Some)
+                }
+                Err(error) => Err(error),
+            }
+        }
+        (PublishFailure::Fork, BlockSource::Wallet { wallet_data, account, intent }) => {
+            // The cached frontier lost against another block, so the backend's view replaces it.
+            let state = reset_account_state(ledger, account, api_key).await?;
+            rebuild(wallet_data, account, &state, intent, signed_block, true).await.map(Some)
+        }
+        (PublishFailure::Fork | PublishFailure::OldBlock | PublishFailure::WorkUnavailable | PublishFailure::Rejected, _) => Ok(None),
+    }
+}
+
+/// Replaces the cached chain state with the backend's view and returns it.
+async fn reset_account_state(ledger: &LedgerPool, account: &WalletAccount, api_key: &str) -> Result<AccountStateApi, ApiErrorResult> {
+    let account_info = match ledger.account_info(&account.address, api_key).await {
+        Ok(account_info) => Some(account_info),
+        Err(error) if is_account_not_found(&error) => None,
+        Err(error) => return Err(error),
+    };
+
+    Ok(match reset_chain_state(&account.address, account_info.as_ref()) {
+        Some(chain_state) => chain_state.account_state(),
+        None => unopened_account_state(account),
+    })
+}
+
+async fn rebuild(
+    wallet_data: &WalletData,
+    account: &WalletAccount,
+    state: &AccountStateApi,
+    intent: BlockIntent<'_>,
+    replaced: &SignedBlock,
+    reuse_work: bool,
+) -> Result<(SignedBlock, &'static str), ApiErrorResult> {
+    let rebuilt = build_account_block(wallet_data, account, state, intent, reuse_work.then_some(replaced)).await?;
+    let recovery = if !rebuilt.work_root.eq_ignore_ascii_case(&replaced.work_root) {
+        "rebuilt_with_new_work"
+    } else if reuse_work {
+        "rebuilt"
+    } else {
+        "regenerated_work"
+    };
+
+    Ok((rebuilt, recovery))
+}
+
+fn publish_attempt(attempt: u32, signed_block: &SignedBlock, outcome: &str, error: Option<String>) -> PublishAttemptApi {
+    PublishAttemptApi {
+        attempt,
+        hash: signed_block.hash.clone(),
+        previous: signed_block.block.previous.clone(),
+        outcome: outcome.to_string(),
+        error,
+        recovery: None,
+    }
+}
+
+/// `1. fork (NODE_RPC_REJECTED: ...) -> rebuilt; 2. published`
+pub fn attempt_trail(attempts: &[PublishAttemptApi]) -> String {
+    attempts.iter()
+        .map(|attempt| {
+            let mut entry = format!("{}. {}", attempt.attempt, attempt.outcome);
+            if let Some(error) = &attempt.error {
+                entry.push_str(&format!(" ({})", error));
+            }
+            if let Some(recovery) = &attempt.recovery {
+                entry.push_str(&format!(" -> {}", recovery));
+            }
+            entry
+        })
+        .collect::<Vec<String>>()
+        .join("; ")
+}
\ No newline at end of file
@@ -0,0 +1,114 @@
+use crate::{enums::api::error::block::BlockError, helpers::nano::{hex_to_bytes, nano_address_to_public_key, state_block_hash, verify_hash_signature, work_value}, 
+    structs::{api::ApiErrorResult, block::StateBlock}};
+
+pub const BASE_THRESH_SEND_CHANGE: u64 = 0xfffffff800000000;
+pub const BASE_THRESH_RECEIVE_OPEN_EPOCH: u64 = 0xfffffe0000000000;
+
+pub const ZERO_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Values derived while validating a block, reused when reporting it.
+#[derive(Debug, Clone)]
+pub struct ValidatedBlock {
+    pub hash: String,
+    pub account_public_key: String,
+    pub work_root: String,
+    pub threshold: u64,
+    pub work_value: u64,
+}
+
+pub fn work_threshold(subtype: &str) -> Option<u64> {
+    match subtype {
+        "send" | "change" => Some(BASE_THRESH_SEND_CHANGE),
+        "receive" | "open" => Some(BASE_THRESH_RECEIVE_OPEN_EPOCH),
+        _ => None,
+    }
+}
+
+/// Checks a state block before it is published: structure for its subtype, balance change against
+/// `previous_balance`, the signature over the recomputed hash, and work against the subtype threshold.
+pub fn validate_block(block: &StateBlock, subtype: &str, previous_balance: u128) -> Result<ValidatedBlock, ApiErrorResult> {
+    check_block(block, subtype, previous_balance, true)
+}
+
+/// Same as `validate_block` but ignores `work`, for signed blocks that get their work attached later.
+/// `work_value` is 0 in the result.
+pub fn validate_unworked_block(block: &StateBlock, subtype: &str, previous_balance: u128) -> Result<ValidatedBlock, ApiErrorResult> {
+    check_block(block, subtype, previous_balance, false)
+}
+
+fn check_block(block: &StateBlock, subtype: &str, previous_balance: u128, check_work: bool) -> Result<ValidatedBlock, ApiErrorResult> {
+    if block.block_type != "state" {
+        return Err(BlockError::InvalidBlockType.to_response());
+    }
+    let threshold = work_threshold(subtype).ok_or_else(|| BlockError::InvalidSubtype.to_response())?;
+
+    let account_pub32 = address_public_key_bytes(&block.account)
+        .ok_or_else(|| BlockError::InvalidAccountAddress.to_response())?;
+    let prev_32 = hex_to_bytes(&block.previous).ok().filter(|bytes| bytes.len() == 32)
+        .ok_or_else(|| BlockError::InvalidPreviousHash.to_response())?;
+    let rep_pub32 = address_public_key_bytes(&block.representative)
+        .ok_or_else(|| BlockError::InvalidRepresentativeAddress.to_response())?;
+    let link_32 = hex_to_bytes(&block.link).ok().filter(|bytes| bytes.len() == 32)
+        .ok_or_else(|| BlockError::InvalidLink.to_response())?;
+    let balance = block.balance.parse::<u128>()
+        .map_err(|_| BlockError::InvalidBalance.to_response())?;
+    let signature = hex_to_bytes(&block.signature).ok().filter(|bytes| bytes.len() == 64)
+        .ok_or_else(|| BlockError::MalformedSignature.to_response())?;
+    let is_open = prev_32.iter().all(|byte| *byte == 0);
+    let zero_link = link_32.iter().all(|byte| *byte == 0);
+    let structure_ok = match subtype {
+        "open" => is_open && previous_balance == 0 && !zero_link,
+        "change" => !is_open && zero_link,
+        _ => !is_open && !zero_link,
+    };
+    if !structure_ok {
+        return Err(BlockError::SubtypeMismatch.to_response());
+    }
+
+    let balance_ok = match subtype {
+        "send" => balance < previous_balance,
+        "receive" | "open" => balance > previous_balance,
+        _ => balance == previous_balance,
+    };
+    if !balance_ok {
+        return Err(BlockError::BalanceMismatch.to_response());
+    }
+
+    let h32 = state_block_hash(&account_pub32, &prev_32, &rep_pub32, balance, &link_32)
+        .map_err(|_| BlockError::BlockHashGenerationFailed.to_response())?;
+    if !verify_hash_signature(&account_pub32, &h32, &signature).unwrap_or(false) {
+        return Err(BlockError::SignatureMismatch.to_response());
+    }
+
+    let work_root_bytes = if is_open { &account_pub32 } else { &prev_32 };
+    let work_val = if check_work { check_work_value(&block.work, work_root_bytes, threshold)? } else { 0 };
+
+    Ok(ValidatedBlock {
+        hash: hex::encode(h32),
+        account_public_key: hex::encode_upper(&account_pub32),
+        work_root: hex::encode(work_root_bytes).to_uppercase(),
+        threshold,
+        work_value: work_val,
+    })
+}
+
+fn check_work_value(work: &str, work_root: &[u8], threshold: u64) -> Result<u64, ApiErrorResult> {
+    let mut work_le = hex_to_bytes(work)
+        .map_err(|_| BlockError::InvalidWorkHex.to_response())?;
+    work_le.reverse();
+    let work_le: [u8; 8] = work_le.try_into()
+        .map_err(|_| BlockError::InvalidWorkLength.to_response())?;
+
+    let work_val = work_value(work_root, &work_le)
+        .map_err(|_| BlockError::CalculateWorkFailed.to_response())?;
+    if work_val < threshold {
+        return Err(BlockError::InsufficientWork.to_response());
+    }
+
+    Ok(work_val)
+}
+
+pub fn address_public_key_bytes(address: &str) -> Option<Vec<u8>> {
+    let public_key = nano_address_to_public_key(address, true).ok()?;
+    hex_to_bytes(&public_key).ok()
+}
@@ -1,8 +1,13 @@
 use std::{env, net::SocketAddr};
 
-use axum::{Router, routing::{get, post}};
+use axum::{Router, routing::{delete, get, post}};
 
-use crate::handlers::{credits::{get_credits_api, topup_credits_api}, donate::donate_api, payment::{create_payment_request_api, get_payment_status_api}, wallet::{get_balance_api, send_nano_api}};
+use crate::handlers::{block::{build_block_api, inspect_block_api, publish_signed_block_api}, credits::{get_credits_api, topup_credits_api}, donate::donate_api, ledger::get_ledger_status_api, message::{sign_message_api, verify_message_api}, payment::{create_payment_request_api, get_payment_qr_api, get_payment_status_api}, 
+    util::{address_to_public_key_api, build_uri_api, derive_addresses_api, parse_uri_api, public_key_to_address_api, validate_address_api}, 
+    wallet::{create_account_api, get_account_queue_api, get_address_qr_api, get_balance_api, list_accounts_api, send_nano_api, send_uri_api, set_representative_api}, 
+    wallets::{create_wallet_api, delete_wallet_api, export_wallet_api, import_wallet_api, list_wallets_api, lock_wallet_api, recover_wallet_api, restore_wallet_api, rotate_password_api, 
+    select_wallet_api, split_wallet_seed_api, unlock_wallet_api}, watch::{add_watch_only_api, get_account_history_api, get_receivable_api, list_watch_only_api, 
+    remove_watch_only_api}};
 
 pub async fn initialize_api_routers() {
     let app = set_routes();
@@ -27,7 +32,40 @@ fn set_routes() -> Router {
     let app = Router::new()
         .route("/wallet/balance", get(get_balance_api))
         .route("/wallet/send", post(send_nano_api)) 
+        .route("/wallet/send/uri", post(send_uri_api))
+        .route("/wallet/representative", post(set_representative_api))
+        .route("/wallet/qr", get(get_address_qr_api))
+        .route("/wallet/accounts", get(list_accounts_api).post(create_account_api))
+        .route("/wallet/watch", get(list_watch_only_api).post(add_watch_only_api))
+        .route("/wallet/watch/remove", post(remove_watch_only_api))
+        .route("/wallet/receivable", get(get_receivable_api))
+        .route("/wallet/history", get(get_account_history_api))
+        .route("/wallet/queue", get(get_account_queue_api))
+        .route("/block/build", post(build_block_api))
+        .route("/block/publish", post(publish_signed_block_api))
+        .route("/block/inspect", post(inspect_block_api))
+        .route("/ledger/status", get(get_ledger_status_api))
+        .route("/message/sign", post(sign_message_api))
+        .route("/message/verify", post(verify_message_api))
+        .route("/util/address/validate", get(validate_address_api))
+        .route("/util/address/public-key", get(address_to_public_key_api))
+        .route("/util/public-key/address", get(public_key_to_address_api))
+        .route("/util/derive", post(derive_addresses_api))
+        .route("/util/uri/parse", get(parse_uri_api))
+        .route("/util/uri", post(build_uri_api))
+        .route("/wallets", get(list_wallets_api).post(create_wallet_api))
+        .route("/wallets/import", post(import_wallet_api))
+        .route("/wallets/password/rotate", post(rotate_password_api))
+        .route("/wallets/unlock", post(unlock_wallet_api))
+        .route("/wallets/lock", post(lock_wallet_api))
+        .route("/wallets/restore", post(restore_wallet_api))
+        .route("/wallets/recover", post(recover_wallet_api))
+        .route("/wallets/{name}/shares", post(split_wallet_seed_api))
+        .route("/wallets/{name}/export", post(export_wallet_api))
+        .route("/wallets/{name}/select", post(select_wallet_api))
+        .route("/wallets/{name}", delete(delete_wallet_api))
         .route("/payment/request", post(create_payment_request_api)) 
+        .route("/payment/request/{transaction_id}/qr", get(get_payment_qr_api))
         .route("/payment/status/{transaction_id}", get(get_payment_status_api)) 
         .route("/credits", get(get_credits_api))
         .route("/credits/topup/{credits_amount}", post(topup_credits_api)) 
@@ -0,0 +1,107 @@
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::enums::api::error::wallet::WalletError;
+use crate::helpers::nano::hex_to_bytes;
+use crate::services::wallet::password::{is_valid_new_password, password_rotation_guard, verify_wallet_password, wallet_password};
+use crate::services::wallet::registry::{register_wallet_entry, reserve_wallet_name, wallet_file_path};
+use crate::services::wallet::session::store_unlocked_wallet;
+use crate::services::wallet::wallet::{decrypt_wallet_data, encrypt_wallet_data, ensure_default_account, get_wallet_data, save_wallet_data};
+use crate::structs::api::ApiErrorResult;
+use crate::structs::wallet::{ExportWalletResponseApi, RestoreWalletRequestApi, WalletRegistryEntry};
+
+pub const BACKUPS_DIR: &str = "data/backups";
+
+const DEFAULT_BACKUPS_TO_KEEP: usize = 10;
+
+/// Number of local backups kept per wallet, from `WALLET_BACKUP_KEEP` (minimum 1).
+fn backups_to_keep() -> usize {
+    std::env::var("WALLET_BACKUP_KEEP")
+        .ok()
+        .and_then(|value| value.trim().parse::<usize>().ok())
+        .unwrap_or(DEFAULT_BACKUPS_TO_KEEP)
+        .max(1)
+}
+
+fn wallet_backup_dir(name: &str) -> PathBuf {
+    PathBuf::from(BACKUPS_DIR).join(name)
+}
+
+/// Copies the current wallet file to `data/backups/<name>/<unix_ms>-<reason>.bin` and prunes the oldest copies.
+/// Called before any operation that overwrites or removes a wallet file.
+pub fn backup_wallet_file(name: &str, reason: &str) -> Result<PathBuf, ApiErrorResult> {
+    let backup_dir = wallet_backup_dir(name);
+    fs::create_dir_all(&backup_dir).map_err(|_| WalletError::BackupFailed.to_response())?;
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis())
+        .unwrap_or(0);
+    let backup_path = backup_dir.join(format!("{:013}-{}.bin", timestamp, reason));
+    fs::copy(wallet_file_path(name), &backup_path).map_err(|_| WalletError::BackupFailed.to_response())?;
+
+    prune_wallet_backups(name);
+
+    Ok(backup_path)
+}
+
+fn prune_wallet_backups(name: &str) {
+    let Ok(entries) = fs::read_dir(wallet_backup_dir(name)) else {
+        return;
+    };
+
+    let mut backups: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|extension| extension == "bin"))
+        .collect();
+    backups.sort();
+
+    let excess = backups.len().saturating_sub(backups_to_keep());
+    for path in backups.into_iter().take(excess) {
+        let _ = fs::remove_file(path);
+    }
+}
+
+/// Encrypts the unlocked wallet into a portable wallet file, hex encoded.
+/// `wallet_password` must be the runtime wallet password, checked before the wallet is read; the backup is encrypted
+/// with `password` when given, otherwise with the runtime wallet password.
+pub fn export_wallet(name: &str, wallet_password: &str, password: Option<&str>) -> Result<ExportWalletResponseApi, ApiErrorResult> {
+    verify_wallet_password(wallet_password)?;
+
+    let password = match password {
+        Some(password) if !is_valid_new_password(password) => return Err(WalletError::InvalidPassword.to_response()),
+        Some(password) => password.to_string(),
+        None => wallet_password.to_string(),
+    };
+
+    let wallet_data = get_wallet_data(Some(name))?;
+    let encrypted_wallet = encrypt_wallet_data(&wallet_data, &password)
+        .map_err(|_| WalletError::WalletSaveFailed.to_response())?;
+
+    Ok(ExportWalletResponseApi {
+        wallet: wallet_data.name.clone(),
+        address: wallet_data.address.clone(),
+        backup: hex::encode_upper(encrypted_wallet),
+    })
+}
+
+/// Restores an exported backup (or a raw local backup file) as a new wallet, re-encrypted with the runtime password.
+/// The stored API key is kept, so the address is not registered upstream again.
+pub fn restore_wallet(request: &RestoreWalletRequestApi) -> Result<WalletRegistryEntry, ApiErrorResult> {
+    let reservation = reserve_wallet_name(request.name.trim())?;
+
+    let encrypted_wallet = hex_to_bytes(&request.backup).map_err(|_| WalletError::InvalidBackup.to_response())?;
+    let password = request.password.clone().unwrap_or_else(wallet_password);
+    let mut wallet_data = decrypt_wallet_data(&encrypted_wallet, &password)
+        .map_err(|_| WalletError::InvalidBackup.to_response())?;
+    wallet_data.name = reservation.name().to_string();
+    ensure_default_account(&mut wallet_data);
+
+    let _rotation_guard = password_rotation_guard();
+    save_wallet_data(&wallet_data)?;
+    let entry = register_wallet_entry(&wallet_data)?;
+    store_unlocked_wallet(wallet_data);
+
+    Ok(entry)
+}
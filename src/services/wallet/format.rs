@@ -0,0 +1,112 @@
+use argon2::{Algorithm, Argon2, Params, Version};
+
+pub const WALLET_FILE_MAGIC: &[u8; 4] = b"NTWL";
+pub const WALLET_FILE_VERSION: u8 = 1;
+
+pub const KDF_ARGON2ID: u8 = 1;
+pub const CIPHER_AES_256_GCM: u8 = 1;
+
+const DEFAULT_M_COST: u32 = 65_536;
+const DEFAULT_T_COST: u32 = 3;
+const DEFAULT_P_COST: u32 = 1;
+
+pub const SALT_LEN: usize = 16;
+pub const NONCE_LEN: usize = 12;
+
+/// Self-describing prefix of an encrypted wallet file:
+/// `magic(4) | version(1) | kdf(1) | m_cost(4 LE) | t_cost(4 LE) | p_cost(4 LE) | cipher(1)`,
+/// followed by `salt(16) | nonce(12) | ciphertext`. The header is authenticated as AES-GCM associated data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WalletFileHeader {
+    pub version: u8,
+    pub kdf: u8,
+    pub m_cost: u32,
+    pub t_cost: u32,
+    pub p_cost: u32,
+    pub cipher: u8,
+}
+
+impl WalletFileHeader {
+    pub const LEN: usize = 19;
+
+    pub fn current() -> Self {
+        WalletFileHeader {
+            version: WALLET_FILE_VERSION,
+            kdf: KDF_ARGON2ID,
+            m_cost: DEFAULT_M_COST,
+            t_cost: DEFAULT_T_COST,
+            p_cost: DEFAULT_P_COST,
+            cipher: CIPHER_AES_256_GCM,
+        }
+    }
+
+    /// Parameters implied by headerless files, which were written with `Argon2::default()`.
+    pub fn legacy() -> Self {
+        WalletFileHeader {
+            version: 0,
+            kdf: KDF_ARGON2ID,
+            m_cost: Params::DEFAULT_M_COST,
+            t_cost: Params::DEFAULT_T_COST,
+            p_cost: Params::DEFAULT_P_COST,
+            cipher: CIPHER_AES_256_GCM,
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(Self::LEN);
+        out.extend_from_slice(WALLET_FILE_MAGIC);
+        out.push(self.version);
+        out.push(self.kdf);
+        out.extend_from_slice(&self.m_cost.to_le_bytes());
+        out.extend_from_slice(&self.t_cost.to_le_bytes());
+        out.extend_from_slice(&self.p_cost.to_le_bytes());
+        out.push(self.cipher);
+        out
+    }
+
+    /// Returns `None` for headerless (legacy) files.
+    pub fn parse(data: &[u8]) -> Option<WalletFileHeader> {
+        if data.len() < Self::LEN || &data[..4] != WALLET_FILE_MAGIC {
+            return None;
+        }
+
+        let u32_at = |offset: usize| u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap());
+        Some(WalletFileHeader {
+            version: data[4],
+            kdf: data[5],
+            m_cost: u32_at(6),
+            t_cost: u32_at(10),
+            p_cost: u32_at(14),
+            cipher: data[18],
+        })
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.version == 0 || self.version > WALLET_FILE_VERSION {
+            return Err(format!("Unsupported wallet file version {}", self.version));
+        }
+        if self.kdf != KDF_ARGON2ID {
+            return Err(format!("Unsupported wallet KDF id {}", self.kdf));
+        }
+        if self.cipher != CIPHER_AES_256_GCM {
+            return Err(format!("Unsupported wallet cipher id {}", self.cipher));
+        }
+
+        Ok(())
+    }
+
+    pub fn derive_key(&self, password: &str, salt: &[u8]) -> Result<[u8; 32], String> {
+        let params = Params::new(self.m_cost, self.t_cost, self.p_cost, Some(32)).map_err(|e| e.to_string())?;
+        let mut key = [0u8; 32];
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(password.as_bytes(), salt, &mut key)
+            .map_err(|e| e.to_string())?;
+
+        Ok(key)
+    }
+}
+
+/// True when the file carries a header with the current version and KDF parameters.
+pub fn is_current_wallet_format(data: &[u8]) -> bool {
+    WalletFileHeader::parse(data).is_some_and(|header| header == WalletFileHeader::current())
+}
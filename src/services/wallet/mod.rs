@@ -1 +1,7 @@
-pub mod wallet;
\ No newline at end of file
+pub mod wallet;
+pub mod registry;
+pub mod format;
+pub mod password;
+pub mod session;
+pub mod backup;
+pub mod shares;
\ No newline at end of file
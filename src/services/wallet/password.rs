@@ -0,0 +1,115 @@
+use std::fs;
+use std::sync::{RwLock, RwLockReadGuard};
+
+use once_cell::sync::Lazy;
+
+use crate::enums::api::error::wallet::WalletError;
+use crate::services::app::initialize::set_env_file_value;
+use crate::services::wallet::backup::backup_wallet_file;
+use crate::services::wallet::registry::{load_wallet_registry, wallet_file_path};
+use crate::services::wallet::wallet::{decrypt_wallet_data, encrypt_wallet_data, lock_wallet_writes, wallet_write_lock, write_file_atomic};
+use crate::structs::api::ApiErrorResult;
+use crate::structs::wallet::RotatePasswordResponseApi;
+
+pub const WALLET_PASSWORD_ENV: &str = "AI_WALLET_PASSWORD";
+
+const MIN_PASSWORD_LENGTH: usize = 12;
+
+/// Set after a rotation so the running process uses the new password without re-reading `.env`.
+static WALLET_PASSWORD: Lazy<RwLock<Option<String>>> = Lazy::new(|| RwLock::new(None));
+/// Held exclusively by a rotation and shared by paths that write a wallet not yet in the registry.
+static PASSWORD_ROTATION_LOCK: RwLock<()> = RwLock::new(());
+
+/// Hold from writing a new wallet file until it is registered, so a concurrent rotation either re-encrypts it
+/// or has not started yet.
+pub fn password_rotation_guard() -> RwLockReadGuard<'static, ()> {
+    PASSWORD_ROTATION_LOCK.read().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+pub fn wallet_password() -> String {
+    configured_wallet_password().expect("AI_WALLET_PASSWORD not set in .env")
+}
+
+fn configured_wallet_password() -> Option<String> {
+    if let Some(password) = WALLET_PASSWORD.read().unwrap().as_ref() {
+        return Some(password.clone());
+    }
+
+    std::env::var(WALLET_PASSWORD_ENV).ok()
+}
+
+/// Fails with `WRONG_PASSWORD` unless `password` is the runtime wallet password. Guards every operation that hands
+/// out the seed or unlocks a wallet.
+pub fn verify_wallet_password(password: &str) -> Result<(), ApiErrorResult> {
+    match configured_wallet_password() {
+        Some(current) if current == password => Ok(()),
+        _ => Err(WalletError::WrongPassword.to_response()),
+    }
+}
+
+/// `.env` values are written single-quoted, so quotes and line breaks cannot be represented.
+pub fn is_valid_new_password(password: &str) -> bool {
+    password.chars().count() >= MIN_PASSWORD_LENGTH
+        && !password.chars().any(|c| c == '\'' || c == '\n' || c == '\r')
+}
+
+/// Re-encrypts every registered wallet with `new_password` (fresh salt and nonce per file),
+/// backing up each previous file to `data/backups/`, then updates `.env`. If `.env` cannot be written, the previous
+/// files are put back and the old password stays in effect.
+/// All files are decrypted and re-encrypted in memory before anything is written. Every wallet's write lock is held
+/// until the new password is in effect, so no concurrent save can write a file under the old password, and the
+/// rotation guard keeps wallets from being created or restored in the meantime.
+pub fn rotate_wallet_password(current_password: &str, new_password: &str) -> Result<RotatePasswordResponseApi, ApiErrorResult> {
+    let _rotation_guard = PASSWORD_ROTATION_LOCK.write().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let registry = load_wallet_registry()?;
+    let locks: Vec<_> = registry.wallets.iter().map(|entry| wallet_write_lock(&entry.name)).collect();
+    let _guards: Vec<_> = locks.iter().map(|lock| lock_wallet_writes(lock)).collect();
+
+    verify_wallet_password(current_password)?;
+    if !is_valid_new_password(new_password) {
+        return Err(WalletError::InvalidPassword.to_response());
+    }
+
+    let mut staged = Vec::with_capacity(registry.wallets.len());
+    for entry in &registry.wallets {
+        let path = wallet_file_path(&entry.name);
+        let encrypted_data = fs::read(&path).map_err(|_| WalletError::WalletNotFound.to_response())?;
+        let wallet_data = decrypt_wallet_data(&encrypted_data, current_password)
+            .map_err(|_| WalletError::WalletDecryptionFailed.to_response())?;
+        let reencrypted = encrypt_wallet_data(&wallet_data, new_password)
+            .map_err(|_| WalletError::WalletSaveFailed.to_response())?;
+
+        staged.push((entry.name.clone(), path, reencrypted));
+    }
+
+    let mut backups = Vec::with_capacity(staged.len());
+    for (name, _, _) in &staged {
+        backups.push(backup_wallet_file(name, "rotate")?);
+    }
+
+    let restore_backups = |count: usize| {
+        for ((_, written_path, _), backup_path) in staged.iter().zip(&backups).take(count) {
+            let _ = fs::copy(backup_path, written_path);
+        }
+    };
+
+    let mut rotated_wallets = Vec::with_capacity(staged.len());
+    for (name, path, reencrypted) in &staged {
+        if write_file_atomic(path, reencrypted).is_err() {
+            restore_backups(rotated_wallets.len() + 1);
+            return Err(WalletError::WalletSaveFailed.to_response());
+        }
+        rotated_wallets.push(name.clone());
+    }
+
+    // A restart reads the password from `.env`, so files re-encrypted without it would no longer open.
+    if set_env_file_value(WALLET_PASSWORD_ENV, &format!("'{}'", new_password)).is_err() {
+        restore_backups(staged.len());
+        return Err(WalletError::EnvUpdateFailed.to_response());
+    }
+    *WALLET_PASSWORD.write().unwrap() = Some(new_password.to_string());
+
+    Ok(RotatePasswordResponseApi {
+        rotated_wallets,
+    })
+}
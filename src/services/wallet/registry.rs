@@ -0,0 +1,259 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use once_cell::sync::Lazy;
+
+use crate::enums::api::error::wallet::WalletError;
+use crate::handlers::wallet::register_wallet_api;
+use crate::helpers::nano::{bip39_mnemonic_to_seed, hex_to_bytes};
+use crate::services::ledger::pool::ledger;
+use crate::services::wallet::backup::backup_wallet_file;
+use crate::services::wallet::password::password_rotation_guard;
+use crate::services::wallet::session::{lock_wallet, store_unlocked_wallet};
+use crate::services::wallet::wallet::{create_wallet_account, derive_wallet_data, generate_wallet_seed, load_wallet_file, lock_wallet_writes, save_wallet_data, wallet_write_lock, write_file_atomic};
+use crate::structs::api::ApiErrorResult;
+use crate::structs::wallet::{ImportWalletRequestApi, WalletData, WalletRegistry, WalletRegistryEntry, WalletSeedType};
+
+pub const MAX_IMPORT_ACCOUNTS: u32 = 100;
+
+pub const WALLETS_DIR: &str = "data/wallets";
+pub const DEFAULT_WALLET_NAME: &str = "default";
+
+const LEGACY_WALLET_FILE_PATH: &str = "data/ai.bin";
+const REGISTRY_FILE_NAME: &str = "registry.json";
+
+static REGISTRY_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+/// Names of wallets being created, so two concurrent creates cannot both find the name free and write the same file.
+static RESERVED_WALLET_NAMES: Lazy<Mutex<HashSet<String>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+pub fn wallet_file_path(name: &str) -> PathBuf {
+    PathBuf::from(WALLETS_DIR).join(format!("{}.bin", name))
+}
+
+fn registry_file_path() -> PathBuf {
+    PathBuf::from(WALLETS_DIR).join(REGISTRY_FILE_NAME)
+}
+
+pub fn is_valid_wallet_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 64
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// A missing registry is empty; an unparsable one is an error so it is never silently replaced with the default.
+pub fn load_wallet_registry() -> Result<WalletRegistry, ApiErrorResult> {
+    match fs::read(registry_file_path()) {
+        Ok(content) => serde_json::from_slice(&content).map_err(|_| WalletError::RegistryCorrupted.to_response()),
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(WalletRegistry::default()),
+        Err(_) => Err(WalletError::RegistryCorrupted.to_response()),
+    }
+}
+
+fn save_wallet_registry(registry: &WalletRegistry) -> Result<(), ApiErrorResult> {
+    let content = serde_json::to_vec_pretty(registry).map_err(|_| WalletError::RegistrySaveFailed.to_response())?;
+    write_file_atomic(&registry_file_path(), &content).map_err(|_| WalletError::RegistrySaveFailed.to_response())
+}
+
+/// Resolves a per-request wallet selector to a registered wallet name, falling back to the selected wallet.
+pub fn resolve_wallet_name(selector: Option<&str>) -> Result<String, ApiErrorResult> {
+    let registry = load_wallet_registry()?;
+    let name = match selector.map(str::trim) {
+        Some(name) if !name.is_empty() => name.to_string(),
+        _ => registry.selected.clone().unwrap_or_else(|| DEFAULT_WALLET_NAME.to_string()),
+    };
+
+    if !registry.wallets.iter().any(|wallet| wallet.name == name) {
+        return Err(WalletError::WalletNotFound.to_response());
+    }
+
+    Ok(name)
+}
+
+pub fn register_wallet_entry(wallet_data: &WalletData) -> Result<WalletRegistryEntry, ApiErrorResult> {
+    let _guard = REGISTRY_LOCK.lock().unwrap();
+    let mut registry = load_wallet_registry()?;
+
+    let entry = WalletRegistryEntry {
+        name: wallet_data.name.clone(),
+        address: wallet_data.address.clone(),
+        created_at: unix_timestamp(),
+    };
+    registry.wallets.retain(|wallet| wallet.name != entry.name);
+    registry.wallets.push(entry.clone());
+    if registry.selected.is_none() {
+        registry.selected = Some(entry.name.clone());
+    }
+    save_wallet_registry(&registry)?;
+
+    Ok(entry)
+}
+
+/// Holds a free wallet name from the availability check until the new wallet is stored; released on drop.
+pub struct WalletNameReservation {
+    name: String,
+}
+
+impl WalletNameReservation {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Drop for WalletNameReservation {
+    fn drop(&mut self) {
+        if let Ok(mut reserved) = RESERVED_WALLET_NAMES.lock() {
+            reserved.remove(&self.name);
+        }
+    }
+}
+
+/// Fails with `WALLET_ALREADY_EXISTS` when a wallet file exists or another create holds the name.
+pub fn reserve_wallet_name(name: &str) -> Result<WalletNameReservation, ApiErrorResult> {
+    if !is_valid_wallet_name(name) {
+        return Err(WalletError::InvalidWalletName.to_response());
+    }
+
+    let mut reserved = RESERVED_WALLET_NAMES.lock().unwrap();
+    if wallet_file_path(name).exists() || !reserved.insert(name.to_string()) {
+        return Err(WalletError::WalletAlreadyExists.to_response());
+    }
+
+    Ok(WalletNameReservation { name: name.to_string() })
+}
+
+pub async fn create_wallet(name: &str) -> Result<WalletRegistryEntry, ApiErrorResult> {
+    let reservation = reserve_wallet_name(name)?;
+
+    let seed_hex = generate_wallet_seed();
+    let wallet_data = derive_wallet_data(WalletSeedType::Nano, &seed_hex, "")
+        .map_err(|_| WalletError::AccountDerivationFailed.to_response())?;
+
+    store_new_wallet(reservation, wallet_data).await
+}
+
+pub async fn import_wallet(request: &ImportWalletRequestApi) -> Result<WalletRegistryEntry, ApiErrorResult> {
+    let reservation = reserve_wallet_name(request.name.trim())?;
+
+    let (seed_type, secret_hex) = match (&request.seed, &request.private_key, &request.mnemonic) {
+        (Some(seed), None, None) => (WalletSeedType::Nano, parse_hex_secret(seed, WalletError::InvalidSeed)?),
+        (None, Some(private_key), None) => (WalletSeedType::PrivateKey, parse_hex_secret(private_key, WalletError::InvalidPrivateKey)?),
+        (None, None, Some(mnemonic)) => {
+            let bip39_seed = bip39_mnemonic_to_seed(mnemonic, request.passphrase.as_deref().unwrap_or(""))
+                .map_err(|_| WalletError::InvalidMnemonic.to_response())?;
+            (WalletSeedType::Bip39, hex::encode_upper(bip39_seed))
+        }
+        _ => return Err(WalletError::InvalidImportSource.to_response()),
+    };
+
+    let account_count = request.accounts.unwrap_or(1).clamp(1, MAX_IMPORT_ACCOUNTS);
+    if seed_type == WalletSeedType::PrivateKey && account_count > 1 {
+        return Err(WalletError::InvalidImportSource.to_response());
+    }
+
+    let mut wallet_data = derive_wallet_data(seed_type, &secret_hex, "")
+        .map_err(|_| WalletError::AccountDerivationFailed.to_response())?;
+    for _ in 1..account_count {
+        create_wallet_account(&mut wallet_data, None)?;
+    }
+
+    store_new_wallet(reservation, wallet_data).await
+}
+
+fn parse_hex_secret(value: &str, error: WalletError) -> Result<String, ApiErrorResult> {
+    match hex_to_bytes(value) {
+        Ok(bytes) if bytes.len() == 32 => Ok(hex::encode_upper(bytes)),
+        _ => Err(error.to_response()),
+    }
+}
+
+/// Registers the wallet's first address upstream for an API key, then writes the encrypted file and registry entry.
+/// Without an ifenpay ledger backend there is no ifenpay account, so the wallet is stored without an API key.
+/// The reservation keeps the name taken across the upstream call and is released once the file is written.
+pub async fn store_new_wallet(reservation: WalletNameReservation, mut wallet_data: WalletData) -> Result<WalletRegistryEntry, ApiErrorResult> {
+    if ledger().uses_ifenpay() {
+        let registration = register_wallet_api(&wallet_data.address, &wallet_data.public_key).await?
+            .0.data.ok_or_else(|| WalletError::WalletRegistrationFailed.to_response())?;
+        wallet_data.api_key = registration.api_key;
+    }
+    wallet_data.name = reservation.name().to_string();
+
+    let _rotation_guard = password_rotation_guard();
+    save_wallet_data(&wallet_data)?;
+    let entry = register_wallet_entry(&wallet_data)?;
+    store_unlocked_wallet(wallet_data);
+
+    Ok(entry)
+}
+
+pub fn select_wallet(name: &str) -> Result<WalletRegistry, ApiErrorResult> {
+    let _guard = REGISTRY_LOCK.lock().unwrap();
+    let mut registry = load_wallet_registry()?;
+    if !registry.wallets.iter().any(|wallet| wallet.name == name) {
+        return Err(WalletError::WalletNotFound.to_response());
+    }
+
+    registry.selected = Some(name.to_string());
+    save_wallet_registry(&registry)?;
+
+    Ok(registry)
+}
+
+pub fn delete_wallet(name: &str) -> Result<WalletRegistry, ApiErrorResult> {
+    let _guard = REGISTRY_LOCK.lock().unwrap();
+    let mut registry = load_wallet_registry()?;
+    if !registry.wallets.iter().any(|wallet| wallet.name == name) {
+        return Err(WalletError::WalletNotFound.to_response());
+    }
+    if registry.selected.as_deref() == Some(name) {
+        return Err(WalletError::WalletInUse.to_response());
+    }
+
+    // Held across removal and session lock so no in-flight save can recreate the file afterwards.
+    let lock = wallet_write_lock(name);
+    let write_guard = lock_wallet_writes(&lock);
+    backup_wallet_file(name, "delete")?;
+    fs::remove_file(wallet_file_path(name)).map_err(|_| WalletError::WalletSaveFailed.to_response())?;
+    lock_wallet(name);
+    drop(write_guard);
+
+    registry.wallets.retain(|wallet| wallet.name != name);
+    save_wallet_registry(&registry)?;
+
+    Ok(registry)
+}
+
+/// Creates the wallet directory, moves a pre-registry `data/ai.bin` into it and
+/// makes sure at least one wallet exists.
+pub async fn initialize_wallet_registry() {
+    fs::create_dir_all(WALLETS_DIR).expect("Failed to create wallets directory");
+
+    let legacy_wallet_file = PathBuf::from(LEGACY_WALLET_FILE_PATH);
+    let default_wallet_file = wallet_file_path(DEFAULT_WALLET_NAME);
+    if legacy_wallet_file.exists() && !default_wallet_file.exists() {
+        fs::rename(&legacy_wallet_file, &default_wallet_file).expect("Failed to move data/ai.bin into data/wallets");
+        eprintln!("✅ Moved data/ai.bin to {}", default_wallet_file.display());
+    }
+
+    let registry = load_wallet_registry().expect("Failed to read wallet registry");
+    if default_wallet_file.exists() && !registry.wallets.iter().any(|wallet| wallet.name == DEFAULT_WALLET_NAME) {
+        let wallet_data = load_wallet_file(DEFAULT_WALLET_NAME)
+            .expect("Failed to unlock default wallet");
+        register_wallet_entry(&wallet_data).expect("Failed to write wallet registry");
+    }
+
+    if load_wallet_registry().expect("Failed to read wallet registry").wallets.is_empty() {
+        create_wallet(DEFAULT_WALLET_NAME).await.expect("Failed to create default wallet");
+        eprintln!("✅ Created {}", default_wallet_file.display());
+    }
+}
+
+fn unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
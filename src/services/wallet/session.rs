@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+
+use crate::enums::api::error::wallet::WalletError;
+use crate::services::wallet::password::verify_wallet_password;
+use crate::services::wallet::registry::load_wallet_registry;
+use crate::services::wallet::wallet::load_wallet_file;
+use crate::structs::api::ApiErrorResult;
+use crate::structs::wallet::{WalletData, WalletSessionStatusApi};
+
+const AUTO_LOCK_SWEEP_INTERVAL: Duration = Duration::from_secs(5);
+
+struct UnlockedWallet {
+    wallet: Arc<WalletData>,
+    last_used: Instant,
+}
+
+/// Decrypted wallets keyed by wallet name. A wallet that is not in this map is locked.
+static UNLOCKED_WALLETS: Lazy<Mutex<HashMap<String, UnlockedWallet>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Idle timeout from `WALLET_AUTO_LOCK_SECONDS`; `0` or unset disables auto-lock.
+fn auto_lock_timeout() -> Option<Duration> {
+    std::env::var("WALLET_AUTO_LOCK_SECONDS")
+        .ok()
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .filter(|seconds| *seconds > 0)
+        .map(Duration::from_secs)
+}
+
+fn is_idle(entry: &UnlockedWallet, timeout: Option<Duration>) -> bool {
+    timeout.is_some_and(|timeout| entry.last_used.elapsed() >= timeout)
+}
+
+pub fn unlocked_wallet(name: &str) -> Result<Arc<WalletData>, ApiErrorResult> {
+    let timeout = auto_lock_timeout();
+    let mut sessions = UNLOCKED_WALLETS.lock().unwrap();
+
+    if sessions.get(name).is_some_and(|entry| is_idle(entry, timeout)) {
+        sessions.remove(name);
+    }
+
+    let entry = sessions.get_mut(name).ok_or_else(|| WalletError::WalletLocked.to_response())?;
+    entry.last_used = Instant::now();
+
+    Ok(Arc::clone(&entry.wallet))
+}
+
+/// Caches an already decrypted wallet, e.g. right after it was created or imported.
+pub fn store_unlocked_wallet(wallet_data: WalletData) {
+    UNLOCKED_WALLETS.lock().unwrap().insert(wallet_data.name.clone(), UnlockedWallet {
+        wallet: Arc::new(wallet_data),
+        last_used: Instant::now(),
+    });
+}
+
+/// Replaces the cached copy after a wallet file was rewritten, without unlocking a locked wallet.
+pub fn refresh_unlocked_wallet(wallet_data: &WalletData) {
+    let mut sessions = UNLOCKED_WALLETS.lock().unwrap();
+    if let Some(entry) = sessions.get_mut(&wallet_data.name) {
+        entry.wallet = Arc::new(wallet_data.clone());
+    }
+}
+
+pub fn unlock_wallet(name: &str, password: &str) -> Result<WalletSessionStatusApi, ApiErrorResult> {
+    verify_wallet_password(password)?;
+
+    let wallet_data = load_wallet_file(name)?;
+    store_unlocked_wallet(wallet_data);
+
+    Ok(wallet_session_status(name))
+}
+
+/// Drops the decrypted copy; secrets are zeroized once in-flight requests release their handles.
+pub fn lock_wallet(name: &str) -> WalletSessionStatusApi {
+    UNLOCKED_WALLETS.lock().unwrap().remove(name);
+
+    wallet_session_status(name)
+}
+
+pub fn lock_all_wallets() -> Result<Vec<WalletSessionStatusApi>, ApiErrorResult> {
+    UNLOCKED_WALLETS.lock().unwrap().clear();
+
+    Ok(load_wallet_registry()?.wallets.iter().map(|wallet| wallet_session_status(&wallet.name)).collect())
+}
+
+pub fn wallet_session_status(name: &str) -> WalletSessionStatusApi {
+    let timeout = auto_lock_timeout();
+    let sessions = UNLOCKED_WALLETS.lock().unwrap();
+    let entry = sessions.get(name).filter(|entry| !is_idle(entry, timeout));
+
+    WalletSessionStatusApi {
+        wallet: name.to_string(),
+        unlocked: entry.is_some(),
+        auto_lock_in_seconds: entry.and_then(|entry| timeout.map(|timeout| timeout.saturating_sub(entry.last_used.elapsed()).as_secs())),
+    }
+}
+
+fn lock_idle_wallets() {
+    let timeout = auto_lock_timeout();
+    if timeout.is_none() {
+        return;
+    }
+
+    let mut locked_names = Vec::new();
+    UNLOCKED_WALLETS.lock().unwrap().retain(|name, entry| {
+        let idle = is_idle(entry, timeout);
+        if idle {
+            locked_names.push(name.clone());
+        }
+        !idle
+    });
+
+    // Logged after the mutex is released; stdout belongs to the MCP JSON-RPC stream.
+    for name in locked_names {
+        eprintln!("🔒 Wallet '{}' auto-locked after inactivity", name);
+    }
+}
+
+/// Unlocks every registered wallet with `AI_WALLET_PASSWORD` unless `WALLET_UNLOCK_ON_START=false`.
+pub fn unlock_wallets_on_start() {
+    let unlock_on_start = std::env::var("WALLET_UNLOCK_ON_START")
+        .map(|value| !value.trim().eq_ignore_ascii_case("false"))
+        .unwrap_or(true);
+    if !unlock_on_start {
+        eprintln!("🔒 Wallets start locked; unlock them via /wallets/unlock");
+        return;
+    }
+
+    let Ok(registry) = load_wallet_registry() else {
+        eprintln!("⚠️  Failed to read the wallet registry; wallets start locked");
+        return;
+    };
+    for entry in registry.wallets {
+        match load_wallet_file(&entry.name) {
+            Ok(wallet_data) => store_unlocked_wallet(wallet_data),
+            Err(_) => eprintln!("⚠️  Failed to unlock wallet '{}' on start", entry.name),
+        }
+    }
+}
+
+/// Periodically evicts idle wallets so their secrets do not linger until the next request.
+pub async fn run_auto_lock_task() {
+    let mut interval = tokio::time::interval(AUTO_LOCK_SWEEP_INTERVAL);
+    loop {
+        interval.tick().await;
+        lock_idle_wallets();
+    }
+}
@@ -0,0 +1,69 @@
+use zeroize::Zeroizing;
+
+use crate::enums::api::error::wallet::WalletError;
+use crate::helpers::nano::{hex_to_bytes, nano_address_to_public_key};
+use crate::helpers::shamir::{SecretShare, combine_shares, split_secret};
+use crate::services::wallet::password::verify_wallet_password;
+use crate::services::wallet::registry::{MAX_IMPORT_ACCOUNTS, reserve_wallet_name, store_new_wallet};
+use crate::services::wallet::wallet::{create_wallet_account, derive_wallet_data, get_wallet_data};
+use crate::structs::api::ApiErrorResult;
+use crate::structs::wallet::{RecoverWalletRequestApi, SplitSeedResponseApi, WalletRegistryEntry, WalletSeedType};
+
+/// Splits the wallet seed into `share_count` printable shares, any `threshold` of which recover it.
+/// `password` must be the runtime wallet password, checked before the seed is touched.
+pub fn split_wallet_seed(name: &str, password: &str, threshold: u8, share_count: u8) -> Result<SplitSeedResponseApi, ApiErrorResult> {
+    verify_wallet_password(password)?;
+
+    let wallet_data = get_wallet_data(Some(name))?;
+    if wallet_data.seed_type == WalletSeedType::PrivateKey {
+        return Err(WalletError::SeedNotAvailable.to_response());
+    }
+
+    let seed = Zeroizing::new(hex_to_bytes(wallet_data.wallet_private_seed.expose())
+        .map_err(|_| WalletError::InvalidSeed.to_response())?);
+    let shares = split_secret(&seed, threshold, share_count)
+        .map_err(|_| WalletError::InvalidShareThreshold.to_response())?;
+
+    Ok(SplitSeedResponseApi {
+        wallet: wallet_data.name.clone(),
+        address: wallet_data.address.clone(),
+        seed_type: wallet_data.seed_type.clone(),
+        threshold,
+        shares: shares.iter().map(SecretShare::encode).collect(),
+    })
+}
+
+/// Reconstructs a seed from shares, checks that it derives `request.address` as account 0
+/// and stores it as a new wallet.
+pub async fn recover_wallet_from_shares(request: &RecoverWalletRequestApi) -> Result<WalletRegistryEntry, ApiErrorResult> {
+    let reservation = reserve_wallet_name(request.name.trim())?;
+
+    let seed_type = request.seed_type.clone().unwrap_or_default();
+    if seed_type == WalletSeedType::PrivateKey {
+        return Err(WalletError::SeedNotAvailable.to_response());
+    }
+    let expected_public_key = nano_address_to_public_key(&request.address, true)?;
+
+    let shares = request.shares.iter()
+        .map(|share| SecretShare::parse(share))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| WalletError::InvalidShares.to_response())?;
+    let seed = combine_shares(&shares).map_err(|_| WalletError::InvalidShares.to_response())?;
+    // Wiped now rather than after the upstream registration below.
+    drop(shares);
+    let seed_hex = Zeroizing::new(hex::encode_upper(seed.as_slice()));
+    drop(seed);
+
+    let mut wallet_data = derive_wallet_data(seed_type, &seed_hex, "")
+        .map_err(|_| WalletError::AccountDerivationFailed.to_response())?;
+    if !wallet_data.public_key.eq_ignore_ascii_case(&expected_public_key) {
+        return Err(WalletError::ShareVerificationFailed.to_response());
+    }
+
+    let account_count = request.accounts.unwrap_or(1).clamp(1, MAX_IMPORT_ACCOUNTS);
+    for _ in 1..account_count {
+        create_wallet_account(&mut wallet_data, None)?;
+    }
+
+    store_new_wallet(reservation, wallet_data).await
+}
@@ -1,16 +1,266 @@
 use aes_gcm::{Aes256Gcm, KeyInit, Nonce};
-use aes_gcm::aead::{Aead, OsRng};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use aes_gcm::aead::{Aead, OsRng, Payload};
 use aes_gcm::aead::rand_core::RngCore;
-use argon2::Argon2;
+use once_cell::sync::Lazy;
 use serde_json;
-use crate::structs::wallet::WalletData;
+use zeroize::Zeroizing;
+use crate::helpers::nano::{bip44_nano_private_key, hex_to_bytes, nano_account_private_key, nano_account_public_key, nano_address_to_public_key, nano_private_key_to_public_key, 
+    public_key_to_nano_address};
+use crate::enums::api::error::{account::AccountError, wallet::WalletError};
+use crate::services::wallet::backup::backup_wallet_file;
+use crate::services::wallet::format::{NONCE_LEN, SALT_LEN, WalletFileHeader, is_current_wallet_format};
+use crate::services::wallet::password::wallet_password;
+use crate::services::wallet::registry::{resolve_wallet_name, wallet_file_path};
+use crate::services::wallet::session::{refresh_unlocked_wallet, unlocked_wallet};
+use crate::structs::api::ApiErrorResult;
+use crate::structs::secret::SecretString;
+use crate::structs::wallet::{MonitoredAccount, WalletAccount, WalletData, WalletSeedType, WatchOnlyAccount};
+
+/// One lock per wallet name, held by everything that rewrites the wallet file so concurrent edits cannot overwrite each other.
+static WALLET_WRITE_LOCKS: Lazy<Mutex<HashMap<String, Arc<Mutex<()>>>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+pub fn wallet_write_lock(name: &str) -> Arc<Mutex<()>> {
+    WALLET_WRITE_LOCKS.lock().unwrap()
+        .entry(name.to_string())
+        .or_default()
+        .clone()
+}
+
+/// A panic in another writer leaves the file itself intact (writes are atomic), so a poisoned lock is still usable.
+pub fn lock_wallet_writes(lock: &Mutex<()>) -> MutexGuard<'_, ()> {
+    lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Returns the unlocked copy of the wallet; fails with `WALLET_LOCKED` instead of touching the file.
+pub fn get_wallet_data(wallet: Option<&str>) -> Result<Arc<WalletData>, ApiErrorResult> {
+    let name = resolve_wallet_name(wallet)?;
+
+    unlocked_wallet(&name)
+}
+
+pub fn load_wallet_file(name: &str) -> Result<WalletData, ApiErrorResult> {
+    let encrypted_data = fs::read(wallet_file_path(name)).map_err(|_| WalletError::WalletNotFound.to_response())?;
+    let wallet_password = wallet_password();
+
+    let mut wallet_data = decrypt_wallet_data(&encrypted_data, &wallet_password)
+        .map_err(|_| WalletError::WalletDecryptionFailed.to_response())?;
+    wallet_data.name = name.to_string();
+    ensure_default_account(&mut wallet_data);
+
+    if !is_current_wallet_format(&encrypted_data) {
+        backup_wallet_file(name, "migrate")?;
+        save_wallet_data(&wallet_data)?;
+        eprintln!("✅ Upgraded wallet file '{}' to format version {}", name, WalletFileHeader::current().version);
+    }
+
+    Ok(wallet_data)
+}
+
+/// Applies `update` to the latest unlocked copy and saves it, holding the wallet's write lock throughout.
+pub fn update_wallet_data<T>(wallet: Option<&str>, update: impl FnOnce(&mut WalletData) -> Result<T, ApiErrorResult>) -> Result<T, ApiErrorResult> {
+    let name = resolve_wallet_name(wallet)?;
+    let lock = wallet_write_lock(&name);
+    let _guard = lock_wallet_writes(&lock);
+
+    let mut wallet_data = unlocked_wallet(&name)?.as_ref().clone();
+    let result = update(&mut wallet_data)?;
+    write_wallet_data(&wallet_data)?;
+
+    Ok(result)
+}
 
-pub fn get_wallet_data() -> WalletData {
-    let file_path = "data/ai.bin";
-    let encrypted_data = std::fs::read(file_path).expect("Failed to read wallet file");
-    let wallet_password = std::env::var("AI_WALLET_PASSWORD").expect("AI_WALLET_PASSWORD not set in .env");
+pub fn save_wallet_data(wallet_data: &WalletData) -> Result<(), ApiErrorResult> {
+    let lock = wallet_write_lock(&wallet_data.name);
+    let _guard = lock_wallet_writes(&lock);
 
-    decrypt_wallet_data(&encrypted_data, &wallet_password).expect("Failed to decrypt wallet data")
+    write_wallet_data(wallet_data)
+}
+
+/// Callers must hold the wallet's write lock.
+fn write_wallet_data(wallet_data: &WalletData) -> Result<(), ApiErrorResult> {
+    let wallet_password = wallet_password();
+    let encrypted_wallet = encrypt_wallet_data(wallet_data, &wallet_password)
+        .map_err(|_| WalletError::WalletSaveFailed.to_response())?;
+
+    write_file_atomic(&wallet_file_path(&wallet_data.name), &encrypted_wallet)
+        .map_err(|_| WalletError::WalletSaveFailed.to_response())?;
+    refresh_unlocked_wallet(wallet_data);
+
+    Ok(())
+}
+
+/// Writes to a sibling temp file and renames it over `path`, so a crash never leaves a truncated wallet.
+pub fn write_file_atomic(path: &Path, content: &[u8]) -> std::io::Result<()> {
+    let tmp_path = path.with_extension("tmp");
+    fs::write(&tmp_path, content)?;
+    fs::rename(&tmp_path, path)
+}
+
+/// Wallet files written before multi-account support only hold account 0.
+pub fn ensure_default_account(wallet_data: &mut WalletData) {
+    if wallet_data.accounts.is_empty() {
+        wallet_data.accounts.push(WalletAccount {
+            index: 0,
+            address: wallet_data.address.clone(),
+            public_key: wallet_data.public_key.clone(),
+            label: None,
+        });
+    }
+
+    let highest_index = wallet_data.accounts.iter().map(|account| account.index).max().unwrap_or(0);
+    if wallet_data.next_index <= highest_index {
+        wallet_data.next_index = highest_index + 1;
+    }
+}
+
+pub fn resolve_wallet_account(wallet_data: &WalletData, selector: Option<&str>) -> Result<WalletAccount, ApiErrorResult> {
+    let selector = match selector.map(str::trim) {
+        Some(selector) if !selector.is_empty() => selector,
+        _ => return wallet_data.accounts.iter()
+            .find(|account| account.index == 0)
+            .or_else(|| wallet_data.accounts.first())
+            .cloned()
+            .ok_or_else(|| WalletError::AccountNotFound.to_response()),
+    };
+
+    let index = selector.parse::<u32>().ok();
+    let account = wallet_data.accounts.iter().find(|account| {
+        Some(account.index) == index
+            || account.address == selector
+            || account.label.as_deref() == Some(selector)
+    });
+
+    match account {
+        Some(account) => Ok(account.clone()),
+        None if find_watch_only_account(wallet_data, selector).is_some() => Err(WalletError::WatchOnlyAccount.to_response()),
+        None => Err(WalletError::AccountNotFound.to_response()),
+    }
+}
+
+pub fn find_watch_only_account<'a>(wallet_data: &'a WalletData, selector: &str) -> Option<&'a WatchOnlyAccount> {
+    let selector = selector.trim();
+    wallet_data.watch_only.iter()
+        .find(|account| account.address == selector || account.label.as_deref() == Some(selector))
+}
+
+/// Like `resolve_wallet_account`, but also accepts watch-only addresses and labels for read-only calls.
+pub fn resolve_monitored_account(wallet_data: &WalletData, selector: Option<&str>) -> Result<MonitoredAccount, ApiErrorResult> {
+    match selector.and_then(|selector| find_watch_only_account(wallet_data, selector)) {
+        Some(account) => Ok(MonitoredAccount::WatchOnly(account.clone())),
+        None => resolve_wallet_account(wallet_data, selector).map(MonitoredAccount::Owned),
+    }
+}
+
+pub fn add_watch_only_account(wallet_data: &mut WalletData, address: &str, label: Option<String>) -> Result<WatchOnlyAccount, ApiErrorResult> {
+    let address = address.trim();
+    let public_key = nano_address_to_public_key(address, true)?;
+    let address = public_key_to_nano_address(&public_key).map_err(|_| AccountError::InvalidAddress.to_response())?;
+
+    let owned = wallet_data.accounts.iter().any(|account| account.address == address);
+    if owned || wallet_data.watch_only.iter().any(|account| account.address == address) {
+        return Err(WalletError::WatchAddressExists.to_response());
+    }
+
+    let account = WatchOnlyAccount {
+        address,
+        public_key: public_key.to_uppercase(),
+        label,
+    };
+    wallet_data.watch_only.push(account.clone());
+
+    Ok(account)
+}
+
+pub fn remove_watch_only_account(wallet_data: &mut WalletData, selector: &str) -> Result<WatchOnlyAccount, ApiErrorResult> {
+    let account = find_watch_only_account(wallet_data, selector)
+        .cloned()
+        .ok_or_else(|| WalletError::AccountNotFound.to_response())?;
+    wallet_data.watch_only.retain(|watched| watched.address != account.address);
+
+    Ok(account)
+}
+
+/// `secret_hex` is the Nano seed, the BIP39 seed or the raw private key, depending on `seed_type`.
+pub fn derive_account_private_key(seed_type: &WalletSeedType, secret_hex: &str, index: u32) -> Result<Zeroizing<Vec<u8>>, String> {
+    let private_key = match seed_type {
+        WalletSeedType::Nano => nano_account_private_key(secret_hex, index)?,
+        WalletSeedType::Bip39 => bip44_nano_private_key(&Zeroizing::new(hex_to_bytes(secret_hex)?), index)?,
+        WalletSeedType::PrivateKey if index == 0 => hex_to_bytes(secret_hex)?,
+        WalletSeedType::PrivateKey => return Err("Private key wallets only hold account 0".to_string()),
+    };
+
+    Ok(Zeroizing::new(private_key))
+}
+
+pub fn wallet_account_private_key(wallet_data: &WalletData, index: u32) -> Result<Zeroizing<Vec<u8>>, String> {
+    derive_account_private_key(&wallet_data.seed_type, wallet_secret_hex(wallet_data), index)
+}
+
+fn wallet_secret_hex(wallet_data: &WalletData) -> &str {
+    match wallet_data.seed_type {
+        WalletSeedType::PrivateKey => wallet_data.private_key.expose(),
+        WalletSeedType::Nano | WalletSeedType::Bip39 => wallet_data.wallet_private_seed.expose(),
+    }
+}
+
+pub fn derive_wallet_account(seed_type: &WalletSeedType, secret_hex: &str, index: u32, label: Option<String>) -> Result<WalletAccount, String> {
+    let public_key = match seed_type {
+        WalletSeedType::Nano => nano_account_public_key(secret_hex, index)?,
+        WalletSeedType::Bip39 | WalletSeedType::PrivateKey => nano_private_key_to_public_key(&derive_account_private_key(seed_type, secret_hex, index)?)?,
+    };
+    let public_key = hex::encode_upper(public_key);
+    let address = public_key_to_nano_address(&public_key)?;
+
+    Ok(WalletAccount {
+        index,
+        address,
+        public_key,
+        label,
+    })
+}
+
+pub fn create_wallet_account(wallet_data: &mut WalletData, label: Option<String>) -> Result<WalletAccount, ApiErrorResult> {
+    let account = derive_wallet_account(&wallet_data.seed_type, wallet_secret_hex(wallet_data), wallet_data.next_index, label)
+        .map_err(|_| WalletError::AccountDerivationFailed.to_response())?;
+
+    wallet_data.accounts.push(account.clone());
+    wallet_data.next_index = account.index + 1;
+
+    Ok(account)
+}
+
+pub fn generate_wallet_seed() -> String {
+    let mut seed = [0u8; 32];
+    OsRng.fill_bytes(&mut seed);
+
+    hex::encode_upper(seed)
+}
+
+pub fn derive_wallet_data(seed_type: WalletSeedType, secret_hex: &str, api_key: &str) -> Result<WalletData, String> {
+    let private_key = derive_account_private_key(&seed_type, secret_hex, 0)?;
+    let account = derive_wallet_account(&seed_type, secret_hex, 0, None)?;
+    let wallet_private_seed = match seed_type {
+        WalletSeedType::PrivateKey => SecretString::default(),
+        WalletSeedType::Nano | WalletSeedType::Bip39 => SecretString::new(secret_hex.to_uppercase()),
+    };
+
+    Ok(WalletData {
+        address: account.address.clone(),
+        public_key: account.public_key.clone(),
+        private_key: SecretString::new(hex::encode_upper(&private_key)),
+        wallet_private_seed,
+        api_key: api_key.to_string(),
+        seed_type,
+        name: String::new(),
+        accounts: vec![account],
+        next_index: 1,
+        watch_only: Vec::new(),
+    })
 }
 
 pub fn encrypt_wallet_data(
@@ -18,27 +268,27 @@ pub fn encrypt_wallet_data(
     password: &str,
 ) -> Result<Vec<u8>, String> {
 
-    let mut salt = [0u8; 16];
-    OsRng.fill_bytes(&mut salt);
+    let header = WalletFileHeader::current();
+    let header_bytes = header.to_bytes();
 
-    let mut key = [0u8; 32];
-    Argon2::default()
-        .hash_password_into(password.as_bytes(), &salt, &mut key)
-        .map_err(|e| e.to_string())?;
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
 
-    let cipher = Aes256Gcm::new_from_slice(&key).map_err(|e| e.to_string())?;
+    let key = Zeroizing::new(header.derive_key(password, &salt)?);
+    let cipher = Aes256Gcm::new_from_slice(key.as_ref()).map_err(|e| e.to_string())?;
 
-    let mut nonce_bytes = [0u8; 12];
+    let mut nonce_bytes = [0u8; NONCE_LEN];
     OsRng.fill_bytes(&mut nonce_bytes);
     let nonce = Nonce::from_slice(&nonce_bytes);
 
-    let plaintext = serde_json::to_vec(wallet_data).map_err(|e| e.to_string())?;
+    let plaintext = Zeroizing::new(serde_json::to_vec(wallet_data).map_err(|e| e.to_string())?);
 
     let ciphertext = cipher
-        .encrypt(nonce, plaintext.as_ref())
+        .encrypt(nonce, Payload { msg: plaintext.as_ref(), aad: &header_bytes })
         .map_err(|_| "Encryption failed".to_string())?;
 
-    let mut output = Vec::with_capacity(16 + 12 + ciphertext.len());
+    let mut output = Vec::with_capacity(header_bytes.len() + SALT_LEN + NONCE_LEN + ciphertext.len());
+    output.extend_from_slice(&header_bytes);
     output.extend_from_slice(&salt);
     output.extend_from_slice(&nonce_bytes);
     output.extend_from_slice(&ciphertext);
@@ -52,28 +302,32 @@ pub fn decrypt_wallet_data(
     password: &str,
 ) -> Result<WalletData, String> {
 
-    if data.len() < 28 {
+    let (header, aad, body) = match WalletFileHeader::parse(data) {
+        Some(header) => {
+            header.validate()?;
+            (header, &data[..WalletFileHeader::LEN], &data[WalletFileHeader::LEN..])
+        }
+        None => (WalletFileHeader::legacy(), &data[..0], data),
+    };
+
+    if body.len() < SALT_LEN + NONCE_LEN {
         return Err("Data too short".into());
     }
 
-    let salt = &data[..16];
-    let nonce = &data[16..28];
-    let ciphertext = &data[28..];
+    let salt = &body[..SALT_LEN];
+    let nonce = &body[SALT_LEN..SALT_LEN + NONCE_LEN];
+    let ciphertext = &body[SALT_LEN + NONCE_LEN..];
 
-    let mut key = [0u8; 32];
-    Argon2::default()
-        .hash_password_into(password.as_bytes(), salt, &mut key)
-        .map_err(|e| e.to_string())?;
-
-    let cipher = Aes256Gcm::new_from_slice(&key).map_err(|e| e.to_string())?;
+    let key = Zeroizing::new(header.derive_key(password, salt)?);
+    let cipher = Aes256Gcm::new_from_slice(key.as_ref()).map_err(|e| e.to_string())?;
     let nonce = Nonce::from_slice(nonce);
 
-    let plaintext = cipher
-        .decrypt(nonce, ciphertext)
-        .map_err(|_| "Wrong password or corrupted data".to_string())?;
+    let plaintext = Zeroizing::new(cipher
+        .decrypt(nonce, Payload { msg: ciphertext, aad })
+        .map_err(|_| "Wrong password or corrupted data".to_string())?);
 
     let wallet: WalletData =
         serde_json::from_slice(&plaintext).map_err(|e| e.to_string())?;
 
     Ok(wallet)
-}
\ No newline at end of file
+}
@@ -1,5 +1,8 @@
+use schemars::JsonSchema;
 use serde::{Serialize, Deserialize};
 
+use crate::structs::api::ApiError;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BlockInfoResponseApi {
     pub block_account: String,
@@ -8,12 +11,12 @@ pub struct BlockInfoResponseApi {
     pub height: String,
     pub local_timestamp: String,
     pub confirmed: String,
-    pub contents: StateBlock,
+    pub contents: LedgerBlock,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub subtype: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct StateBlock {
     #[serde(rename = "type")]
     pub block_type: String, // Always "state"
@@ -26,6 +29,108 @@ pub struct StateBlock {
     pub work: String,
 }
 
+/// Block contents as stored in the ledger. Accounts opened before state blocks still have legacy blocks in their chains.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum LedgerBlock {
+    State(StateBlock),
+    Legacy(LegacyBlock),
+}
+
+impl LedgerBlock {
+    /// Balance after the block. Only state and legacy send blocks carry it.
+    pub fn balance(&self) -> Option<u128> {
+        match self {
+            LedgerBlock::State(block) => block.balance.trim().parse().ok(),
+            LedgerBlock::Legacy(block) => block.balance(),
+        }
+    }
+}
+
+/// Pre-state block types. Each has its own field layout, and its hash covers only the fields listed first, without a preamble.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum LegacyBlock {
+    Send {
+        previous: String,
+        destination: String,
+        /// Balance after the send; 32 hex characters in node RPC output.
+        balance: String,
+        signature: String,
+        work: String,
+    },
+    Receive {
+        previous: String,
+        /// Hash of the received send block.
+        source: String,
+        signature: String,
+        work: String,
+    },
+    /// First block of an account; its work root is the account public key.
+    Open {
+        source: String,
+        representative: String,
+        account: String,
+        signature: String,
+        work: String,
+    },
+    Change {
+        previous: String,
+        representative: String,
+        signature: String,
+        work: String,
+    },
+}
+
+impl LegacyBlock {
+    pub fn block_type(&self) -> &'static str {
+        match self {
+            LegacyBlock::Send { .. } => "send",
+            LegacyBlock::Receive { .. } => "receive",
+            LegacyBlock::Open { .. } => "open",
+            LegacyBlock::Change { .. } => "change",
+        }
+    }
+
+    /// Balance after a send. Legacy send balances are 128-bit big-endian hex; plain decimal is accepted as well.
+    pub fn balance(&self) -> Option<u128> {
+        let LegacyBlock::Send { balance, .. } = self else {
+            return None;
+        };
+
+        let balance = balance.trim();
+        if balance.len() == 32 && balance.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+            u128::from_str_radix(balance, 16).ok()
+        } else {
+            balance.parse().ok()
+        }
+    }
+
+    pub fn signature(&self) -> &str {
+        match self {
+            LegacyBlock::Send { signature, .. } | LegacyBlock::Receive { signature, .. }
+                | LegacyBlock::Open { signature, .. } | LegacyBlock::Change { signature, .. } => signature,
+        }
+    }
+
+    pub fn work(&self) -> &str {
+        match self {
+            LegacyBlock::Send { work, .. } | LegacyBlock::Receive { work, .. }
+                | LegacyBlock::Open { work, .. } | LegacyBlock::Change { work, .. } => work,
+        }
+    }
+}
+
+/// What a new state block should do. Receives on an account without blocks become `open`.
+#[derive(Debug, Clone, Copy)]
+pub enum BlockIntent<'a> {
+    /// `link` is the recipient public key.
+    Send { amount_raw: u128, link: &'a str },
+    /// `link` is the hash of the pending send block.
+    Receive { amount_raw: u128, link: &'a str },
+    Change { representative_public_key: &'a str },
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SignedBlock {
     pub block: StateBlock,
@@ -37,6 +142,141 @@ pub struct SignedBlock {
     pub work_value_hex: String,
 }
 
+/// Account state a block is built on. For offline signing it is supplied by the caller instead of fetched.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AccountStateApi {
+    /// Hash of the current head block.
+    pub frontier: String,
+    /// Current balance in raw.
+    pub balance: String,
+    /// Current representative address.
+    pub representative: String,
+}
+
+/// A signed block that may still lack work, exported for later publishing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OfflineBlockApi {
+    pub subtype: String,
+    pub block: StateBlock,
+    pub hash: String,
+    /// Balance in raw before this block; needed again by `/block/publish`.
+    pub previous_balance: String,
+    pub work_root: String,
+    pub threshold: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum BlockActionApi {
+    Send,
+    Receive,
+    Change,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BuildBlockRequestApi {
+    pub action: BlockActionApi,
+    /// Current account state. Omit for an account without blocks; a receive then becomes `open`.
+    pub state: Option<AccountStateApi>,
+    /// Recipient address (send).
+    pub destination: Option<String>,
+    /// Hash of the pending send block (receive).
+    pub source: Option<String>,
+    /// Amount in Nano (send and receive). Use either this or `amount_raw`.
+    pub amount: Option<String>,
+    /// Amount in raw.
+    pub amount_raw: Option<String>,
+    /// New representative address (change).
+    pub representative: Option<String>,
+    /// Precomputed work (16 hex). Leave empty to attach work when publishing.
+    pub work: Option<String>,
+    /// Wallet name. Defaults to the selected wallet.
+    pub wallet: Option<String>,
+    /// Signing account index, address or label. Defaults to account 0.
+    pub account: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PublishSignedBlockRequestApi {
+    pub subtype: String,
+    pub block: StateBlock,
+    /// Balance in raw before this block, as exported by `/block/build`.
+    pub previous_balance: String,
+    /// Work to attach. Generated by the work server when neither this nor `block.work` is set.
+    pub work: Option<String>,
+    /// Wallet whose API key is used for publishing. Defaults to the selected wallet.
+    pub wallet: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublishedBlockApi {
+    pub account: String,
+    pub subtype: String,
+    pub hash: String,
+    /// Operations on this account that were queued ahead of this one.
+    pub queue_position: usize,
+    /// Every publish attempt, the successful one last.
+    pub attempts: Vec<PublishAttemptApi>,
+}
+
+/// One try at publishing a block and what was done about its failure.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublishAttemptApi {
+    pub attempt: u32,
+    pub hash: String,
+    pub previous: String,
+    /// published, old_block, fork, gap_previous, insufficient_work, unreachable or rejected
+    pub outcome: String,
+    pub error: Option<String>,
+    /// Done before the next attempt: rebuilt (re-signed on a new frontier, same work root), rebuilt_with_new_work,
+    /// regenerated_work, waited_for_previous or retried; `failed: <error>` when recovering failed itself.
+    /// `null` on the last attempt.
+    pub recovery: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct InspectBlockRequestApi {
+    /// Block to inspect. Use exactly one of `block`, `binary` or `hash`.
+    pub block: Option<StateBlock>,
+    /// Canonical 216-byte binary state block as hex.
+    pub binary: Option<String>,
+    /// Hash of a block to fetch from the ledger.
+    pub hash: Option<String>,
+    /// Balance in raw before the block. Fetched from the previous block when omitted.
+    pub previous_balance: Option<String>,
+    /// Wallet whose API key is used for ledger lookups. Defaults to the selected wallet.
+    pub wallet: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockInspectionApi {
+    pub hash: String,
+    pub account: String,
+    /// send, receive, open, change or epoch; null when the previous balance is unknown and the link is ambiguous.
+    pub subtype: Option<String>,
+    pub previous_balance: Option<String>,
+    pub balance: String,
+    pub amount_raw: Option<String>,
+    pub amount: Option<String>,
+    pub link: String,
+    /// Link read as a recipient address (send).
+    pub destination: Option<String>,
+    /// Link read as the hash of the received send block (receive, open).
+    pub source: Option<String>,
+    /// Whether the signature verifies against the block account (epoch blocks are signed by the epoch signer).
+    pub signature_valid: bool,
+    pub work_root: String,
+    pub work_value: Option<String>,
+    pub meets_send_threshold: bool,
+    pub meets_receive_threshold: bool,
+    /// Whether the block passes the same checks as before publishing.
+    pub publishable: bool,
+    /// First failed pre-publish check, if any.
+    pub validation_error: Option<ApiError>,
+    /// Canonical 216-byte binary form as hex; null when the signature or work is malformed.
+    pub binary: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PublishBlockRequestApi {
     pub subtype: String,
@@ -40,4 +40,8 @@ pub struct TopupCreditsSuccessResponse {
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TopupCreditsRequestApi {
     pub credits_amount: u32,
+    /// Wallet name. Defaults to the selected wallet.
+    pub wallet: Option<String>,
+    /// Paying account index, address or label. Defaults to account 0.
+    pub account: Option<String>,
 }
@@ -16,4 +16,8 @@ pub struct DonateAddressResponse {
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct DonateRequestApi {
     pub amount: String,
+    /// Wallet name. Defaults to the selected wallet.
+    pub wallet: Option<String>,
+    /// Donating account index, address or label. Defaults to account 0.
+    pub account: Option<String>,
 }
\ No newline at end of file
@@ -0,0 +1,69 @@
+use serde::{Serialize, Deserialize};
+
+use crate::structs::block::AccountStateApi;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LedgerBackendStatusApi {
+    /// Position in `NANO_LEDGER_BACKENDS`; lower is preferred.
+    pub position: usize,
+    /// ifenpay or node
+    pub kind: String,
+    pub url: String,
+    /// Whether reads currently go to this backend before the unhealthy ones.
+    pub healthy: bool,
+    /// 0-100, a moving average of recent successes.
+    pub score: u32,
+    pub successes: u64,
+    pub failures: u64,
+    pub consecutive_failures: u32,
+    pub last_latency_ms: Option<u64>,
+    pub last_error: Option<String>,
+    pub last_success_at: Option<u64>,
+    pub last_failure_at: Option<u64>,
+}
+
+/// One call to one backend.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LedgerRequestRecordApi {
+    pub at: u64,
+    /// account_info, balance, receivable, block_info, account_history or publish
+    pub operation: String,
+    pub backend: String,
+    /// served, rejected (the backend answered with an error), failed (unreachable; the next backend was tried) or duplicate
+    pub outcome: String,
+    pub error: Option<String>,
+    pub latency_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LedgerStatusResponseApi {
+    pub backends: Vec<LedgerBackendStatusApi>,
+    /// Most recent first.
+    pub recent_requests: Vec<LedgerRequestRecordApi>,
+}
+
+/// Locally known head of an account chain, kept in `data/chain_state.json`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccountChainState {
+    pub frontier: String,
+    /// Balance in raw.
+    pub balance: String,
+    pub representative: String,
+    /// Number of blocks in the chain; null when neither the backend nor our own blocks told us.
+    pub height: Option<u64>,
+    /// Frontiers preceding blocks we published that the backend has not reported yet.
+    /// A backend still reporting one of these is lagging behind us, not ahead.
+    #[serde(default)]
+    pub lagging_frontiers: Vec<String>,
+    pub updated_at: u64,
+}
+
+impl AccountChainState {
+    pub fn account_state(&self) -> AccountStateApi {
+        AccountStateApi {
+            frontier: self.frontier.clone(),
+            balance: self.balance.clone(),
+            representative: self.representative.clone(),
+        }
+    }
+}
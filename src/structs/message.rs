@@ -0,0 +1,36 @@
+use schemars::JsonSchema;
+use serde::{Serialize, Deserialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SignMessageRequestApi {
+    /// UTF-8 message to sign.
+    pub message: String,
+    /// Wallet name. Defaults to the selected wallet.
+    pub wallet: Option<String>,
+    /// Signing account index, address or label. Defaults to account 0.
+    pub account: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignMessageResponseApi {
+    pub account: String,
+    pub message: String,
+    /// Hash of the dummy state block that was signed.
+    pub hash: String,
+    pub signature: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct VerifyMessageRequestApi {
+    pub message: String,
+    /// Address of the claimed signer.
+    pub address: String,
+    /// 128-hex ed25519-blake2b signature.
+    pub signature: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerifyMessageResponseApi {
+    pub account: String,
+    pub valid: bool,
+}
@@ -4,4 +4,10 @@ pub mod payment;
 pub mod donate;
 pub mod api;
 pub mod block;
-pub mod mcp;
\ No newline at end of file
+pub mod mcp;
+pub mod secret;
+pub mod message;
+pub mod util;
+pub mod uri;
+pub mod qr;
+pub mod ledger;
\ No newline at end of file
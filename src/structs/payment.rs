@@ -6,6 +6,9 @@ pub struct CreatePaymentRequestApi{
     pub receive_address: String,
     pub amount: String,
     pub redirect_url: Option<String>,
+    /// Wallet name. Defaults to the selected wallet.
+    #[serde(skip_serializing)]
+    pub wallet: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -13,6 +16,9 @@ pub struct CreatePaymentResponseApi{
     pub receive_address: String,
     pub amount: String,
     pub transaction_id: String,
+    /// `nano:` URI for the exact amount, ready to share with the payer.
+    #[serde(default)]
+    pub payment_uri: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -26,4 +32,6 @@ pub struct StatusPaymentnApi {
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct PaymentStatusRequestApi {
     pub transaction_id: String,
+    /// Wallet name. Defaults to the selected wallet.
+    pub wallet: Option<String>,
 }
\ No newline at end of file
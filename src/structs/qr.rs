@@ -0,0 +1,45 @@
+use schemars::JsonSchema;
+use serde::{Serialize, Deserialize};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum QrFormat {
+    #[default]
+    Svg,
+    Png,
+    /// Plain text for terminals.
+    Ascii,
+}
+
+impl QrFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            QrFormat::Svg => "image/svg+xml",
+            QrFormat::Png => "image/png",
+            QrFormat::Ascii => "text/plain; charset=utf-8",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentQrQueryApi {
+    pub format: Option<QrFormat>,
+    /// Minimum image width in pixels (SVG and PNG). Defaults to 256.
+    pub size: Option<u32>,
+    /// Wallet name. Defaults to the selected wallet.
+    pub wallet: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddressQrQueryApi {
+    /// Wallet name. Defaults to the selected wallet.
+    pub wallet: Option<String>,
+    /// Account index, address or label (including watch-only). Defaults to account 0.
+    pub account: Option<String>,
+    /// Requested amount in Nano, encoded into the `nano:` URI.
+    pub amount: Option<String>,
+    pub label: Option<String>,
+    pub format: Option<QrFormat>,
+    /// Minimum image width in pixels (SVG and PNG). Defaults to 256.
+    pub size: Option<u32>,
+}
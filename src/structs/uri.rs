@@ -0,0 +1,46 @@
+use schemars::JsonSchema;
+use serde::{Serialize, Deserialize};
+
+/// Parsed form of `nano:<address>?amount=<raw>&label=<text>&message=<text>`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NanoUriApi {
+    pub address: String,
+    pub amount_raw: Option<String>,
+    /// `amount_raw` converted to Nano.
+    pub amount: Option<String>,
+    pub label: Option<String>,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ParseUriRequestApi {
+    pub uri: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BuildUriRequestApi {
+    pub address: String,
+    /// Amount in Nano. Use either this or `amount_raw`.
+    pub amount: Option<String>,
+    /// Amount in raw.
+    pub amount_raw: Option<String>,
+    pub label: Option<String>,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuildUriResponseApi {
+    pub uri: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SendUriRequestApi {
+    /// `nano:` payment URI.
+    pub uri: String,
+    /// Amount in Nano, only used when the URI has no amount.
+    pub amount: Option<String>,
+    /// Wallet name. Defaults to the selected wallet.
+    pub wallet: Option<String>,
+    /// Sending account index, address or label. Defaults to account 0.
+    pub account: Option<String>,
+}
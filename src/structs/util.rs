@@ -0,0 +1,72 @@
+use schemars::JsonSchema;
+use serde::{Serialize, Deserialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AddressValidationReason {
+    MissingPrefix,
+    InvalidLength,
+    InvalidCharacter,
+    InvalidChecksum,
+}
+
+impl AddressValidationReason {
+    pub fn message(&self) -> &'static str {
+        match self {
+            AddressValidationReason::MissingPrefix => "Address must start with 'nano_' or 'xrb_'",
+            AddressValidationReason::InvalidLength => "Address must have 60 characters after the prefix",
+            AddressValidationReason::InvalidCharacter => "Address contains characters outside the Nano base32 alphabet",
+            AddressValidationReason::InvalidChecksum => "Address checksum does not match its public key",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AddressQueryApi {
+    pub address: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PublicKeyQueryApi {
+    /// 64-hex public key.
+    pub public_key: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidateAddressResponseApi {
+    pub address: String,
+    pub valid: bool,
+    /// `nano_`-prefixed form of a valid address.
+    pub normalized: Option<String>,
+    pub public_key: Option<String>,
+    pub reason: Option<AddressValidationReason>,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddressPublicKeyApi {
+    pub address: String,
+    pub public_key: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DeriveAddressesRequestApi {
+    /// 64-hex Nano seed.
+    pub seed: String,
+    /// First account index. Defaults to 0.
+    pub start: Option<u32>,
+    /// Number of addresses to derive. Defaults to 1, max 100.
+    pub count: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DerivedAddressApi {
+    pub index: u32,
+    pub address: String,
+    pub public_key: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeriveAddressesResponseApi {
+    pub addresses: Vec<DerivedAddressApi>,
+}
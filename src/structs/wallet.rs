@@ -3,12 +3,302 @@ use std::collections::HashMap;
 use schemars::JsonSchema;
 use serde::{Serialize, Deserialize};
 
+use crate::structs::{block::PublishAttemptApi, secret::SecretString};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WalletData {
     pub address: String,
     pub public_key: String,
-    pub private_key: String,
-    pub wallet_private_seed: String,
+    pub private_key: SecretString,
+    pub wallet_private_seed: SecretString,
+    pub api_key: String,
+    #[serde(default)]
+    pub seed_type: WalletSeedType,
+    #[serde(skip)]
+    pub name: String,
+    #[serde(default)]
+    pub accounts: Vec<WalletAccount>,
+    #[serde(default)]
+    pub next_index: u32,
+    #[serde(default)]
+    pub watch_only: Vec<WatchOnlyAccount>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WalletSeedType {
+    #[default]
+    Nano,
+    Bip39,
+    PrivateKey,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletAccount {
+    pub index: u32,
+    pub address: String,
+    pub public_key: String,
+    pub label: Option<String>,
+}
+
+/// An external address monitored by a wallet without any key material.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchOnlyAccount {
+    pub address: String,
+    pub public_key: String,
+    pub label: Option<String>,
+}
+
+/// Account resolved from a selector: one the wallet can sign for, or a watch-only address.
+#[derive(Debug, Clone)]
+pub enum MonitoredAccount {
+    Owned(WalletAccount),
+    WatchOnly(WatchOnlyAccount),
+}
+
+impl MonitoredAccount {
+    pub fn address(&self) -> &str {
+        match self {
+            MonitoredAccount::Owned(account) => &account.address,
+            MonitoredAccount::WatchOnly(account) => &account.address,
+        }
+    }
+
+    pub fn is_watch_only(&self) -> bool {
+        matches!(self, MonitoredAccount::WatchOnly(_))
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct WalletSelectorApi {
+    /// Wallet name. Defaults to the selected wallet.
+    pub wallet: Option<String>,
+    /// Account index, address or label (including watch-only addresses and labels). Defaults to account 0.
+    pub account: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct AccountHistoryQueryApi {
+    /// Wallet name. Defaults to the selected wallet.
+    pub wallet: Option<String>,
+    /// Account index, address or label (including watch-only addresses and labels). Defaults to account 0.
+    pub account: Option<String>,
+    /// Number of history entries to return. Defaults to 20, max 500.
+    pub count: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AddWatchOnlyRequestApi {
+    pub address: String,
+    pub label: Option<String>,
+    /// Wallet name. Defaults to the selected wallet.
+    pub wallet: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RemoveWatchOnlyRequestApi {
+    /// Watch-only address or label.
+    pub address: String,
+    /// Wallet name. Defaults to the selected wallet.
+    pub wallet: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchOnlyAccountBalanceApi {
+    pub address: String,
+    pub label: Option<String>,
+    pub balance: Option<BalanceResponseApi>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchOnlyAccountsResponseApi {
+    pub accounts: Vec<WatchOnlyAccountBalanceApi>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReceivableBlocksResponseApi {
+    pub account: String,
+    pub watch_only: bool,
+    pub blocks: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountHistoryEntryApi {
+    /// send or receive for state blocks; legacy entries may also be open or change.
+    #[serde(rename = "type")]
+    pub entry_type: String,
+    /// Counterparty account. Legacy change entries have none.
+    #[serde(default)]
+    pub account: Option<String>,
+    #[serde(default)]
+    pub amount: Option<String>,
+    pub hash: String,
+    pub height: Option<String>,
+    pub local_timestamp: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subtype: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountHistoryResponseApi {
+    pub account: String,
+    pub history: Vec<AccountHistoryEntryApi>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CreateAccountRequestApi {
+    pub label: Option<String>,
+    /// Wallet name. Defaults to the selected wallet.
+    pub wallet: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WalletRegistry {
+    pub selected: Option<String>,
+    pub wallets: Vec<WalletRegistryEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletRegistryEntry {
+    pub name: String,
+    pub address: String,
+    pub created_at: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CreateWalletRequestApi {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ImportWalletRequestApi {
+    pub name: String,
+    /// 64-hex Nano seed.
+    pub seed: Option<String>,
+    /// 64-hex account private key.
+    pub private_key: Option<String>,
+    /// BIP39 mnemonic, derived along m/44'/165'/i'.
+    pub mnemonic: Option<String>,
+    /// Optional BIP39 passphrase.
+    pub passphrase: Option<String>,
+    /// Number of accounts to derive. Defaults to 1.
+    pub accounts: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RotatePasswordRequestApi {
+    pub current_password: String,
+    pub new_password: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RotatePasswordResponseApi {
+    pub rotated_wallets: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnlockWalletRequestApi {
+    pub wallet: Option<String>,
+    pub password: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct LockWalletRequestApi {
+    /// Wallet to lock. Locks every wallet when omitted.
+    pub wallet: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletSessionStatusApi {
+    pub wallet: String,
+    pub unlocked: bool,
+    /// Seconds until the idle auto-lock fires; absent when locked or auto-lock is disabled.
+    pub auto_lock_in_seconds: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExportWalletRequestApi {
+    /// Runtime wallet password; the backup holds the seed, so it is only produced for a caller who knows it.
+    pub wallet_password: String,
+    /// Password for the backup. Defaults to the runtime wallet password.
+    pub password: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportWalletResponseApi {
+    pub wallet: String,
+    pub address: String,
+    /// Hex-encoded wallet file (`NTWL` header, salt, nonce, ciphertext).
+    pub backup: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestoreWalletRequestApi {
+    pub name: String,
+    pub backup: String,
+    /// Password the backup was exported with. Defaults to the runtime wallet password.
+    pub password: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SplitSeedRequestApi {
+    /// Runtime wallet password; the shares reveal the seed, so they are only produced for a caller who knows it.
+    pub password: String,
+    /// Shares required to recover the seed (at least 2).
+    pub threshold: u8,
+    /// Shares to create (at most 255).
+    pub shares: u8,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SplitSeedResponseApi {
+    pub wallet: String,
+    pub address: String,
+    pub seed_type: WalletSeedType,
+    pub threshold: u8,
+    /// Shares in `<threshold>-<index>-<HEX>` form.
+    pub shares: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecoverWalletRequestApi {
+    pub name: String,
+    pub shares: Vec<String>,
+    /// Account 0 address the recovered seed must derive.
+    pub address: String,
+    /// Seed type of the split wallet. Defaults to `nano`.
+    pub seed_type: Option<WalletSeedType>,
+    /// Number of accounts to derive. Defaults to 1.
+    pub accounts: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct WalletNameRequestApi {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletAccountBalanceApi {
+    pub index: u32,
+    pub address: String,
+    pub label: Option<String>,
+    pub balance: Option<BalanceResponseApi>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletAccountsResponseApi {
+    pub accounts: Vec<WalletAccountBalanceApi>,
+    pub next_index: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegisterWalletRequestApi {
+    pub address: String,
+    pub public_key: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegisterWalletResponseApi {
     pub api_key: String,
 }
 
@@ -33,16 +323,69 @@ pub struct AccountInfoResponseApi {
     pub representative: String,
     pub difficulty_send: String,
     pub difficulty_receive: String,
+    #[serde(default)]
+    pub block_count: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SendNanoRequestApi {
     pub recipient_address: String,
     pub amount: String, // In Nano
+    /// Wallet name. Defaults to the selected wallet.
+    pub wallet: Option<String>,
+    /// Sending account index, address or label. Defaults to account 0.
+    pub account: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SendNanoResponseApi{
+    pub account: String,
     pub amount: String,
     pub recipient: String, // In Nano
+    /// Hash of the published send block.
+    pub hash: String,
+    /// Operations on this account that were queued ahead of this one.
+    pub queue_position: usize,
+    /// Every publish attempt, the successful one last.
+    pub attempts: Vec<PublishAttemptApi>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SetRepresentativeRequestApi {
+    /// `nano_` address of the new representative.
+    pub representative: String,
+    /// Wallet name. Defaults to the selected wallet.
+    pub wallet: Option<String>,
+    /// Account index, address or label. Defaults to account 0.
+    pub account: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetRepresentativeResponseApi {
+    pub account: String,
+    pub representative: String,
+    /// Hash of the published change block.
+    pub hash: String,
+    /// Operations on this account that were queued ahead of this one.
+    pub queue_position: usize,
+    /// Every publish attempt, the successful one last.
+    pub attempts: Vec<PublishAttemptApi>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueuedOperationApi {
+    pub ticket: u64,
+    /// send, receive, change, publish or topup
+    pub operation: String,
+    pub enqueued_at: u64,
+    /// Whether the block is being built or published right now; the others wait in order.
+    pub running: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountQueueApi {
+    pub account: String,
+    pub depth: usize,
+    /// In execution order.
+    pub operations: Vec<QueuedOperationApi>,
 }
\ No newline at end of file
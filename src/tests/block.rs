@@ -0,0 +1,334 @@
+#![cfg(test)]
+
+use crate::helpers::block::{STATE_BLOCK_SIZE, decode_state_block, encode_state_block, legacy_block_hash};
+use crate::helpers::nano::{state_block_hash, verify_hash_signature};
+use crate::services::nano::inspect::{inspect_block, inspect_legacy_block};
+use crate::services::nano::block::{attach_work, offline_block, sign_block_offline, unopened_account_state};
+use crate::services::nano::validation::validate_block;
+use crate::services::wallet::wallet::derive_wallet_data;
+use crate::structs::api::ApiErrorResult;
+use crate::structs::block::{AccountStateApi, BlockIntent, LedgerBlock, LegacyBlock, StateBlock};
+use crate::structs::wallet::{AccountHistoryEntryApi, WalletSeedType};
+
+const ADDRESS: &str = "nano_3i1aq1cchnmbn9x5rsbap8b15akfh7wj7pwskuzi7ahz8oq6cobd99d4r3b7";
+const ZERO_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Open block of seed 0 / index 0 receiving 1000 raw, with precomputed work for the receive threshold.
+fn open_block() -> StateBlock {
+    StateBlock {
+        block_type: "state".to_string(),
+        account: ADDRESS.to_string(),
+        previous: ZERO_HASH.to_string(),
+        representative: ADDRESS.to_string(),
+        balance: "1000".to_string(),
+        link: "11".repeat(32),
+        signature: "91C806D283C4AEF01D6D0697AF11F12EC14CB14AAE0ECDA2FD48D4AA6169FD156FBFFE0F02527EB5B7FEE9629B9CC95DAEF16401E0F2496CED16A22F06C5B200".to_string(),
+        work: "0000000000f4d315".to_string(),
+    }
+}
+
+fn error_code<T: std::fmt::Debug>(result: Result<T, ApiErrorResult>) -> String {
+    result.unwrap_err().1.0.error.unwrap().error
+}
+
+#[test]
+fn test_validate_open_block() {
+    let validated = validate_block(&open_block(), "open", 0).unwrap();
+
+    assert_eq!(validated.hash, "3e9fa507ab4bf3ef70b4a2575daf433299aaa253ba8d0bb42e7da90e36d59ce6");
+    assert_eq!(validated.work_root, "C008B814A7D269A1FA3C6528B19201A24D797912DB9996FF02A1FF356E45552B");
+    assert!(validated.work_value >= validated.threshold);
+}
+
+#[test]
+fn test_validate_block_rejections() {
+    assert_eq!(error_code(validate_block(&open_block(), "epoch", 0)), "INVALID_SUBTYPE");
+    assert_eq!(error_code(validate_block(&open_block(), "receive", 0)), "SUBTYPE_MISMATCH");
+    assert_eq!(error_code(validate_block(&open_block(), "open", 1)), "SUBTYPE_MISMATCH");
+
+    let mut block = open_block();
+    block.block_type = "send".to_string();
+    assert_eq!(error_code(validate_block(&block, "open", 0)), "INVALID_BLOCK_TYPE");
+
+    let mut block = open_block();
+    block.balance = "0".to_string();
+    assert_eq!(error_code(validate_block(&block, "open", 0)), "BALANCE_MISMATCH");
+
+    let mut block = open_block();
+    block.balance = "1001".to_string();
+    assert_eq!(error_code(validate_block(&block, "open", 0)), "SIGNATURE_MISMATCH");
+
+    let mut block = open_block();
+    block.signature.truncate(126);
+    assert_eq!(error_code(validate_block(&block, "open", 0)), "MALFORMED_SIGNATURE");
+
+    let mut block = open_block();
+    block.work = "0000000000000000".to_string();
+    assert_eq!(error_code(validate_block(&block, "open", 0)), "INSUFFICIENT_WORK");
+}
+
+#[test]
+fn test_validate_change_block_structure() {
+    let mut block = open_block();
+    block.previous = "22".repeat(32);
+    assert_eq!(error_code(validate_block(&block, "change", 1000)), "SUBTYPE_MISMATCH");
+
+    block.link = ZERO_HASH.to_string();
+    block.balance = "999".to_string();
+    assert_eq!(error_code(validate_block(&block, "change", 1000)), "BALANCE_MISMATCH");
+}
+
+#[test]
+fn test_sign_block_offline() {
+    let wallet_data = derive_wallet_data(WalletSeedType::Nano, ZERO_HASH, "test-api-key").unwrap();
+    let account = wallet_data.accounts[0].clone();
+    let link = "11".repeat(32);
+
+    let open = sign_block_offline(&wallet_data, &account, &unopened_account_state(&account), BlockIntent::Receive { amount_raw: 1000, link: &link }).unwrap();
+    assert_eq!(open.subtype, "open");
+    assert_eq!(open.previous_balance, "0");
+    assert_eq!(open.threshold, "fffffe0000000000");
+    assert_eq!(open.work_root, account.public_key.to_uppercase());
+    assert!(open.block.work.is_empty());
+    assert_eq!(error_code(attach_work(open, "0000000000000000")), "INSUFFICIENT_WORK");
+
+    let unopened_change = sign_block_offline(&wallet_data, &account, &unopened_account_state(&account), BlockIntent::Change { representative_public_key: &link });
+    assert_eq!(error_code(unopened_change), "ACCOUNT_NOT_OPENED");
+
+    let state = AccountStateApi {
+        frontier: "22".repeat(32),
+        balance: "1000".to_string(),
+        representative: ADDRESS.to_string(),
+    };
+    let send = sign_block_offline(&wallet_data, &account, &state, BlockIntent::Send { amount_raw: 400, link: &link }).unwrap();
+    assert_eq!((send.subtype.as_str(), send.block.balance.as_str(), send.threshold.as_str()), ("send", "600", "fffffff800000000"));
+    assert_eq!(send.block.representative, ADDRESS);
+    assert_eq!(send.work_root, "22".repeat(32));
+
+    let overdraft = sign_block_offline(&wallet_data, &account, &state, BlockIntent::Send { amount_raw: 1001, link: &link });
+    assert_eq!(error_code(overdraft), "INSUFFICIENT_FUNDS");
+}
+
+#[test]
+fn test_attach_work_to_exported_block() {
+    let mut block = open_block();
+    let work = std::mem::take(&mut block.work);
+
+    let exported = offline_block(block, "open", 0).unwrap();
+    let signed_block = attach_work(exported, &work.to_uppercase()).unwrap();
+
+    assert_eq!(signed_block.hash, "3e9fa507ab4bf3ef70b4a2575daf433299aaa253ba8d0bb42e7da90e36d59ce6");
+    assert_eq!(signed_block.block.work, work);
+}
+
+#[test]
+fn test_inspect_open_block() {
+    let inspection = inspect_block(&open_block(), None).unwrap();
+
+    assert_eq!(inspection.hash, "3e9fa507ab4bf3ef70b4a2575daf433299aaa253ba8d0bb42e7da90e36d59ce6");
+    assert_eq!(inspection.subtype.as_deref(), Some("open"));
+    assert_eq!(inspection.amount_raw.as_deref(), Some("1000"));
+    assert_eq!(inspection.source, Some("11".repeat(32)));
+    assert_eq!(inspection.destination, None);
+    assert!(inspection.signature_valid);
+    assert!(inspection.meets_receive_threshold);
+    assert!(inspection.publishable);
+    assert!(inspection.validation_error.is_none());
+
+    let mut tampered = open_block();
+    tampered.balance = "1001".to_string();
+    let inspection = inspect_block(&tampered, None).unwrap();
+    assert!(!inspection.signature_valid);
+    assert!(!inspection.publishable);
+    assert_eq!(inspection.validation_error.unwrap().error, "SIGNATURE_MISMATCH");
+}
+
+#[test]
+fn test_inspect_infers_subtype_from_balance_and_link() {
+    let mut block = open_block();
+    block.previous = "22".repeat(32);
+    block.work = "zz".to_string();
+
+    let unknown = inspect_block(&block, None).unwrap();
+    assert_eq!(unknown.subtype, None);
+    assert!(unknown.destination.is_some() && unknown.source.is_some());
+    assert_eq!(unknown.work_value, None);
+    assert!(!unknown.publishable);
+
+    let send = inspect_block(&block, Some(1_000_000_000_000_000_000_000_000_001_000)).unwrap();
+    assert_eq!(send.subtype.as_deref(), Some("send"));
+    assert_eq!(send.amount.as_deref(), Some("1"));
+    assert_eq!(send.source, None);
+
+    assert_eq!(inspect_block(&block, Some(10)).unwrap().subtype.as_deref(), Some("receive"));
+
+    block.link = ZERO_HASH.to_string();
+    assert_eq!(inspect_block(&block, None).unwrap().subtype.as_deref(), Some("change"));
+
+    block.link = "65706F636820763220626C6F636B000000000000000000000000000000000000".to_string();
+    let epoch = inspect_block(&block, Some(1000)).unwrap();
+    assert_eq!(epoch.subtype.as_deref(), Some("epoch"));
+    assert!(epoch.validation_error.is_none());
+}
+
+/// Live network state block, as used in the Nano RPC documentation examples. Its signature verifies against
+/// `LIVE_BLOCK_HASH` and its work meets the epoch 1 threshold, so every field is exactly as published.
+fn live_state_block() -> StateBlock {
+    StateBlock {
+        block_type: "state".to_string(),
+        account: "nano_1qato4k7z3spc8gq1zyd8xeqfbzsoxwo36a45ozbrxcatut7up8ohyardu1z".to_string(),
+        previous: "6CDDA48608C7843A0AC1122BDD46D9E20E21190986B19EAC23E7F33F2E6A6766".to_string(),
+        representative: "nano_3pczxuorp48td8645bs3m6c3xotxd3idskrenmi65rbrga5zmkemzhwkaznh".to_string(),
+        balance: "40200000001000000000000000000000000".to_string(),
+        link: "87434F8041869A01C8F6F263B87972D7BA443A72E0A97D7A3FD0CCC2358FD6F9".to_string(),
+        signature: "A5DB164F6B81648F914E49CAB533900C389FAAD64FBB24F6902F9261312B29F730D07E9BCCD21D918301419B4E05B181637CF8419ED4DCBF8EF2539EB2467F07".to_string(),
+        work: "000bc55b014e807d".to_string(),
+    }
+}
+
+const LIVE_BLOCK_HASH: &str = "E2FB233EF4554077A7BF1AA85851D5BF0B36965D2B0FB504B2BC778AB89917D3";
+
+/// The node's `state_block::serialize` layout: account, previous, representative, balance (16 bytes big-endian),
+/// link, signature, then work as 8 bytes big-endian (unlike legacy blocks, which store work little-endian).
+fn live_state_block_binary() -> String {
+    [
+        "5D1AA8A45F8736519D707FCB375976A7F9AF795091021D7E9C7548D6F45DD8D5",
+        "6CDDA48608C7843A0AC1122BDD46D9E20E21190986B19EAC23E7F33F2E6A6766",
+        "D95FEEEB8B08DA598821A72199141ED75D5860BCCB0CA4E041E1387207F9C993",
+        "0007BE035591757A055EE57FA1000000",
+        "87434F8041869A01C8F6F263B87972D7BA443A72E0A97D7A3FD0CCC2358FD6F9",
+        "A5DB164F6B81648F914E49CAB533900C389FAAD64FBB24F6902F9261312B29F730D07E9BCCD21D918301419B4E05B181637CF8419ED4DCBF8EF2539EB2467F07",
+        "000BC55B014E807D",
+    ].concat()
+}
+
+#[test]
+fn test_state_block_binary_round_trip() {
+    let bytes = hex::decode(live_state_block_binary()).unwrap();
+    assert_eq!(bytes.len(), STATE_BLOCK_SIZE);
+
+    let decoded = decode_state_block(&bytes).unwrap();
+    let live = live_state_block();
+    assert_eq!(decoded.account, live.account);
+    assert_eq!(decoded.previous, live.previous);
+    assert_eq!(decoded.representative, live.representative);
+    assert_eq!(decoded.balance, live.balance);
+    assert_eq!(decoded.link.to_uppercase(), live.link);
+    assert_eq!(decoded.signature.to_uppercase(), live.signature);
+    assert_eq!(decoded.work, live.work);
+    assert_eq!(encode_state_block(&live).unwrap().to_vec(), bytes);
+
+    let hash = state_block_hash(&bytes[0..32].to_vec(), &bytes[32..64].to_vec(), &bytes[64..96].to_vec(),
+        u128::from_be_bytes(bytes[96..112].try_into().unwrap()), &bytes[112..144].to_vec()).unwrap();
+    assert_eq!(hex::encode_upper(&hash), LIVE_BLOCK_HASH);
+    assert!(verify_hash_signature(&bytes[0..32], &hash, &bytes[144..208]).unwrap());
+
+    let open_bytes = encode_state_block(&open_block()).unwrap();
+    assert_eq!(decode_state_block(&open_bytes).unwrap().balance, "1000");
+    assert_eq!(inspect_block(&open_block(), None).unwrap().binary, Some(hex::encode_upper(open_bytes)));
+}
+
+#[test]
+fn test_state_block_binary_rejects_bad_input() {
+    assert_eq!(error_code(decode_state_block(&[0u8; STATE_BLOCK_SIZE - 1])), "INVALID_BLOCK_LENGTH");
+
+    let mut block = open_block();
+    block.work = "f4d315".to_string();
+    assert_eq!(error_code(encode_state_block(&block)), "INVALID_WORK_LENGTH");
+
+    let mut block = open_block();
+    block.balance = u128::MAX.to_string();
+    let decoded = decode_state_block(&encode_state_block(&block).unwrap()).unwrap();
+    assert_eq!(decoded.balance, u128::MAX.to_string());
+}
+
+/// Open block of the live network genesis account.
+fn genesis_open_block() -> LegacyBlock {
+    serde_json::from_value(serde_json::json!({
+        "type": "open",
+        "source": "E89208DD038FBB269987689621D52292AE9C35941A7484756ECCED92A65093BA",
+        "representative": "nano_3t6k35gi95xu6tergt6p69ck76ogmitsa8mnijtpxm9fkcm736xtoncuohr3",
+        "account": "nano_3t6k35gi95xu6tergt6p69ck76ogmitsa8mnijtpxm9fkcm736xtoncuohr3",
+        "work": "62f05417dd3fb691",
+        "signature": "9F0C933C8ADE004D808EA1985FA746A7E95BA2A38F867640F53EC8F180BDFE9E2C1268DEAD7C2664F356E37ABA362BC58E46DBA03E523A7B5A19E4B6EB12BB02"
+    })).unwrap()
+}
+
+#[test]
+fn test_legacy_block_hashes() {
+    assert_eq!(hex::encode(legacy_block_hash(&genesis_open_block()).unwrap()), "991cf190094c00f0b68e2e5f75f6bee95a2e0bd93ceaa4a6734db9f19b728948");
+
+    let previous = "22".repeat(32);
+    let send = LegacyBlock::Send {
+        previous: previous.clone(),
+        destination: ADDRESS.to_string(),
+        balance: "000000000000000000000000000003E8".to_string(),
+        signature: String::new(),
+        work: String::new(),
+    };
+    assert_eq!(send.balance(), Some(1000));
+    assert_eq!(hex::encode(legacy_block_hash(&send).unwrap()), "27f53052ae1ad0a6b5499619ac99448332bb53d53bd8fcde18341b320da91fa4");
+
+    let receive = LegacyBlock::Receive { previous: previous.clone(), source: "33".repeat(32), signature: String::new(), work: String::new() };
+    assert_eq!(hex::encode(legacy_block_hash(&receive).unwrap()), "cb6d2629e032ff314758555713c75a9edbabd5747ba0f72b59b6a6ed61fd78ed");
+
+    let change = LegacyBlock::Change { previous, representative: ADDRESS.to_string(), signature: String::new(), work: String::new() };
+    assert_eq!(hex::encode(legacy_block_hash(&change).unwrap()), "96ff8a0ac9106ebbedb8771b88d7cf7f1092c967471472aa74aa991e9b6329a8");
+
+    let bad_previous = LegacyBlock::Receive { previous: "22".to_string(), source: "33".repeat(32), signature: String::new(), work: String::new() };
+    assert_eq!(error_code(legacy_block_hash(&bad_previous)), "INVALID_PREVIOUS_HASH");
+}
+
+#[test]
+fn test_ledger_block_deserializes_state_and_legacy() {
+    let state: LedgerBlock = serde_json::from_value(serde_json::to_value(open_block()).unwrap()).unwrap();
+    assert!(matches!(state, LedgerBlock::State(_)));
+    assert_eq!(state.balance(), Some(1000));
+
+    let send: LedgerBlock = serde_json::from_value(serde_json::json!({
+        "type": "send",
+        "previous": "22".repeat(32),
+        "destination": ADDRESS,
+        "balance": "0000000000000000000000000000FFFF",
+        "work": "0000000000000000",
+        "signature": "00".repeat(64)
+    })).unwrap();
+    assert!(matches!(send, LedgerBlock::Legacy(LegacyBlock::Send { .. })));
+    assert_eq!(send.balance(), Some(65535));
+
+    let change: LedgerBlock = serde_json::from_value(serde_json::json!({
+        "type": "change",
+        "previous": "22".repeat(32),
+        "representative": ADDRESS,
+        "work": "0000000000000000",
+        "signature": "00".repeat(64)
+    })).unwrap();
+    assert!(matches!(change, LedgerBlock::Legacy(LegacyBlock::Change { .. })));
+    assert_eq!(change.balance(), None);
+
+    let entry: AccountHistoryEntryApi = serde_json::from_value(serde_json::json!({
+        "type": "change",
+        "hash": "44".repeat(32),
+        "height": "3",
+        "local_timestamp": "0"
+    })).unwrap();
+    assert_eq!(entry.entry_type, "change");
+    assert!(entry.account.is_none() && entry.amount.is_none());
+}
+
+#[test]
+fn test_inspect_legacy_open_block() {
+    let account = "nano_3t6k35gi95xu6tergt6p69ck76ogmitsa8mnijtpxm9fkcm736xtoncuohr3";
+    let balance = u128::MAX;
+    let inspection = inspect_legacy_block(&genesis_open_block(), account, balance, Some(balance)).unwrap();
+
+    assert_eq!(inspection.hash, "991cf190094c00f0b68e2e5f75f6bee95a2e0bd93ceaa4a6734db9f19b728948");
+    assert_eq!(inspection.subtype.as_deref(), Some("open"));
+    assert_eq!(inspection.previous_balance.as_deref(), Some("0"));
+    assert_eq!(inspection.source.as_deref(), Some("E89208DD038FBB269987689621D52292AE9C35941A7484756ECCED92A65093BA"));
+    assert_eq!(inspection.work_root, "E89208DD038FBB269987689621D52292AE9C35941A7484756ECCED92A65093BA");
+    assert!(inspection.signature_valid);
+    assert!(!inspection.publishable);
+    assert_eq!(inspection.validation_error.unwrap().error, "INVALID_BLOCK_TYPE");
+    assert!(inspection.binary.is_none());
+}
@@ -0,0 +1,343 @@
+#![cfg(test)]
+
+use axum::{Json, Router, http::StatusCode, routing::post};
+use serde_json::{Value, json};
+
+use std::collections::BTreeMap;
+
+use crate::enums::api::error::node::NodeRpcError;
+use crate::helpers::nano::nano_address_to_public_key;
+use crate::services::ledger::backend::LedgerBackend;
+use crate::services::ledger::chain::{advance_chain_state, reconciled_chain_state};
+use crate::services::ledger::node::{node_list, node_rpc_call};
+use crate::services::ledger::pool::{LedgerPool, is_account_not_found, is_duplicate_publish, is_transport_failure, parse_backend_list};
+use crate::structs::block::{BlockInfoResponseApi, LedgerBlock, PublishBlockRequestApi, SignedBlock, StateBlock};
+use crate::structs::wallet::{AccountHistoryEntryApi, AccountInfoResponseApi};
+
+#[test]
+fn test_node_list_accepts_empty_string() {
+    assert!(node_list::<String>(Some(json!(""))).unwrap().is_empty());
+    assert!(node_list::<String>(None).unwrap().is_empty());
+    assert_eq!(node_list::<String>(Some(json!(["AB", "CD"]))).unwrap(), vec!["AB", "CD"]);
+    assert!(node_list::<String>(Some(json!({ "AB": "1" }))).is_err());
+
+    let history = node_list::<AccountHistoryEntryApi>(Some(json!([{
+        "type": "receive",
+        "account": "nano_3i1aq1cchnmbn9x5rsbap8b15akfh7wj7pwskuzi7ahz8oq6cobd99d4r3b7",
+        "amount": "1000",
+        "local_timestamp": "1700000000",
+        "height": "1",
+        "hash": "44".repeat(32),
+        "confirmed": "true"
+    }]))).unwrap();
+    assert_eq!(history[0].amount.as_deref(), Some("1000"));
+}
+
+#[test]
+fn test_node_block_info_response() {
+    let info: BlockInfoResponseApi = serde_json::from_value(json!({
+        "block_account": "nano_3i1aq1cchnmbn9x5rsbap8b15akfh7wj7pwskuzi7ahz8oq6cobd99d4r3b7",
+        "amount": "1000",
+        "balance": "1000",
+        "height": "1",
+        "local_timestamp": "1700000000",
+        "successor": "0".repeat(64),
+        "confirmed": "true",
+        "contents": {
+            "type": "state",
+            "account": "nano_3i1aq1cchnmbn9x5rsbap8b15akfh7wj7pwskuzi7ahz8oq6cobd99d4r3b7",
+            "previous": "0".repeat(64),
+            "representative": "nano_3i1aq1cchnmbn9x5rsbap8b15akfh7wj7pwskuzi7ahz8oq6cobd99d4r3b7",
+            "balance": "1000",
+            "link": "11".repeat(32),
+            "link_as_account": "nano_1aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+            "signature": "00".repeat(64),
+            "work": "0000000000000000"
+        },
+        "subtype": "open"
+    })).unwrap();
+
+    assert!(matches!(info.contents, LedgerBlock::State(_)));
+    assert_eq!(info.subtype.as_deref(), Some("open"));
+}
+
+#[tokio::test]
+async fn test_node_rpc_unreachable() {
+    let result = node_rpc_call::<serde_json::Value>("http://127.0.0.1:9", json!({ "action": "version" })).await;
+
+    assert_eq!(result.unwrap_err().1.0.error.unwrap().error, "NODE_RPC_UNREACHABLE");
+}
+
+const UNREACHABLE_RPC_URL: &str = "http://127.0.0.1:9";
+const ADDRESS: &str = "nano_3i1aq1cchnmbn9x5rsbap8b15akfh7wj7pwskuzi7ahz8oq6cobd99d4r3b7";
+
+fn published_block(previous: &str, hash: &str, balance: &str) -> SignedBlock {
+    SignedBlock {
+        block: StateBlock {
+            block_type: "state".to_string(),
+            account: ADDRESS.to_string(),
+            previous: previous.to_string(),
+            representative: ADDRESS.to_string(),
+            balance: balance.to_string(),
+            link: "11".repeat(32),
+            signature: "00".repeat(64),
+            work: "0000000000000000".to_string(),
+        },
+        hash: hash.to_string(),
+        account_public_key: String::new(),
+        subtype: String::new(),
+        work_root: String::new(),
+        threshold_hex: String::new(),
+        work_value_hex: String::new(),
+    }
+}
+
+fn account_info(frontier: &str, balance: &str, block_count: Option<&str>) -> AccountInfoResponseApi {
+    AccountInfoResponseApi {
+        frontier: frontier.to_string(),
+        balance: balance.to_string(),
+        representative: ADDRESS.to_string(),
+        difficulty_send: String::new(),
+        difficulty_receive: String::new(),
+        block_count: block_count.map(str::to_string),
+    }
+}
+
+/// Minimal node RPC answering `account_balance`, and `process` with `process_reply`.
+async fn spawn_mock_node(process_reply: Value) -> String {
+    spawn_scripted_node(move |request| match request["action"].as_str() {
+        Some("account_balance") => json!({ "balance": "1000", "pending": "0", "receivable": "0" }),
+        Some("process") => process_reply.clone(),
+        _ => json!({ "error": "Unknown command" }),
+    }).await
+}
+
+/// Node RPC (or work server) answering each request with `reply(request)`.
+pub async fn spawn_scripted_node(reply: impl Fn(&Value) -> Value + Clone + Send + Sync + 'static) -> String {
+    let app = Router::new().route("/", post(move |Json(request): Json<Value>| {
+        let reply = reply.clone();
+        async move { Json(reply(&request)) }
+    }));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    format!("http://{}", address)
+}
+
+/// ifenpay API answering every request like it passes through a node error: HTTP 500 with `RPC_RESPONSE_ERROR`.
+async fn spawn_ifenpay_node_error(node_error: &str) -> String {
+    let body = json!({
+        "success": false,
+        "data": null,
+        "error": { "error": "RPC_RESPONSE_ERROR", "message": format!("RPC response indicates an error: {}", node_error) },
+    });
+    let app = Router::new().fallback(move || {
+        let body = body.clone();
+        async move { (StatusCode::INTERNAL_SERVER_ERROR, Json(body)) }
+    });
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    format!("http://{}", address)
+}
+
+#[test]
+fn test_parse_backend_list() {
+    let backends = parse_backend_list(" node:http://10.0.0.1:7076, ifenpay:https://api.example.com ,bogus:x,, ");
+
+    assert_eq!(backends, vec![
+        LedgerBackend::NodeRpc("http://10.0.0.1:7076".to_string()),
+        LedgerBackend::Ifenpay("https://api.example.com".to_string()),
+    ]);
+}
+
+#[tokio::test]
+async fn test_reads_fail_over_to_next_backend() {
+    let mock_url = spawn_mock_node(json!({})).await;
+    let pool = LedgerPool::new(vec![LedgerBackend::NodeRpc(UNREACHABLE_RPC_URL.to_string()), LedgerBackend::NodeRpc(mock_url.clone())]);
+
+    let balance = pool.balance("nano_3i1aq1cchnmbn9x5rsbap8b15akfh7wj7pwskuzi7ahz8oq6cobd99d4r3b7", "").await.unwrap();
+    assert_eq!(balance.balance_raw, "1000");
+
+    let status = pool.status();
+    assert_eq!(status.backends[0].failures, 1);
+    assert_eq!(status.backends[0].score, 80);
+    assert_eq!(status.backends[1].successes, 1);
+    assert_eq!(status.recent_requests[0].outcome, "served");
+    assert!(status.recent_requests[0].backend.ends_with(&mock_url));
+    assert_eq!(status.recent_requests[1].outcome, "failed");
+
+    let rejected = pool.account_history("nano_3i1aq1cchnmbn9x5rsbap8b15akfh7wj7pwskuzi7ahz8oq6cobd99d4r3b7", 5, "").await;
+    assert_eq!(rejected.unwrap_err().1.0.error.unwrap().error, "NODE_RPC_REJECTED");
+    assert_eq!(pool.status().recent_requests[0].outcome, "rejected");
+}
+
+/// A node error passed through by the ifenpay API is an answer: reads return it without failing over and
+/// publishes count it as a rejection, and neither lowers the backend's health.
+#[tokio::test]
+async fn test_ifenpay_node_errors_are_not_transport_failures() {
+    let fallback_url = spawn_mock_node(json!({ "hash": "55".repeat(32) })).await;
+    let not_found_url = spawn_ifenpay_node_error("Account not found").await;
+    let pool = LedgerPool::new(vec![LedgerBackend::Ifenpay(not_found_url), LedgerBackend::NodeRpc(fallback_url)]);
+
+    let error = pool.account_info(ADDRESS, "").await.unwrap_err();
+    assert!(is_account_not_found(&error));
+    assert!(!is_transport_failure(&error));
+    let status = pool.status();
+    assert_eq!(status.recent_requests.len(), 1);
+    assert_eq!(status.recent_requests[0].outcome, "rejected");
+    assert_eq!((status.backends[0].failures, status.backends[0].score), (0, 100));
+
+    let bad_signature_url = spawn_ifenpay_node_error("Bad signature").await;
+    let pool: &'static LedgerPool = Box::leak(Box::new(LedgerPool::new(vec![LedgerBackend::Ifenpay(bad_signature_url)])));
+    let request = PublishBlockRequestApi { subtype: "send".to_string(), block: published_block(&"22".repeat(32), &"66".repeat(32), "0").block };
+
+    let error = pool.publish(&request, &"66".repeat(32), "").await.unwrap_err();
+    assert_eq!(error.1.0.error.unwrap().error, "RPC_RESPONSE_ERROR");
+    assert_eq!(pool.status().recent_requests[0].outcome, "rejected");
+    assert_eq!(pool.status().backends[0].failures, 0);
+}
+
+#[tokio::test]
+async fn test_publish_to_all_with_dedup() {
+    let accepting_url = spawn_mock_node(json!({ "hash": "55".repeat(32) })).await;
+    let known_url = spawn_mock_node(json!({ "error": "Old block" })).await;
+    let pool: &'static LedgerPool = Box::leak(Box::new(LedgerPool::new(vec![
+        LedgerBackend::NodeRpc(accepting_url),
+        LedgerBackend::NodeRpc(known_url),
+        LedgerBackend::NodeRpc(UNREACHABLE_RPC_URL.to_string()),
+    ])));
+    let request = PublishBlockRequestApi {
+        subtype: "open".to_string(),
+        block: StateBlock {
+            block_type: "state".to_string(),
+            account: "nano_3i1aq1cchnmbn9x5rsbap8b15akfh7wj7pwskuzi7ahz8oq6cobd99d4r3b7".to_string(),
+            previous: "0".repeat(64),
+            representative: "nano_3i1aq1cchnmbn9x5rsbap8b15akfh7wj7pwskuzi7ahz8oq6cobd99d4r3b7".to_string(),
+            balance: "1000".to_string(),
+            link: "11".repeat(32),
+            signature: "00".repeat(64),
+            work: "0000000000000000".to_string(),
+        },
+    };
+
+    pool.publish(&request, &"55".repeat(32), "").await.unwrap();
+    let mut outcomes: Vec<String> = pool.status().recent_requests.into_iter().map(|record| record.outcome).collect();
+    outcomes.sort();
+    assert_eq!(outcomes, vec!["duplicate", "failed", "served"]);
+
+    pool.publish(&request, &"55".repeat(32), "").await.unwrap();
+    let latest = &pool.status().recent_requests[0];
+    assert_eq!((latest.backend.as_str(), latest.outcome.as_str()), ("local", "duplicate"));
+}
+
+#[tokio::test]
+async fn test_only_old_block_counts_as_duplicate() {
+    let rejected = |details: &str| NodeRpcError::Rejected(details.to_string()).to_response();
+    assert!(is_duplicate_publish(&rejected("Old block")));
+    assert!(!is_duplicate_publish(&rejected("already processing fork")));
+    assert!(!is_duplicate_publish(&rejected("Old block, but also something else")));
+    assert!(!is_duplicate_publish(&NodeRpcError::Unreachable.to_response()));
+
+    let forking_url = spawn_mock_node(json!({ "error": "already processing fork" })).await;
+    let pool: &'static LedgerPool = Box::leak(Box::new(LedgerPool::new(vec![LedgerBackend::NodeRpc(forking_url)])));
+    let request = PublishBlockRequestApi {
+        subtype: "send".to_string(),
+        block: StateBlock {
+            block_type: "state".to_string(),
+            account: "nano_3i1aq1cchnmbn9x5rsbap8b15akfh7wj7pwskuzi7ahz8oq6cobd99d4r3b7".to_string(),
+            previous: "22".repeat(32),
+            representative: "nano_3i1aq1cchnmbn9x5rsbap8b15akfh7wj7pwskuzi7ahz8oq6cobd99d4r3b7".to_string(),
+            balance: "0".to_string(),
+            link: "33".repeat(32),
+            signature: "00".repeat(64),
+            work: "0000000000000000".to_string(),
+        },
+    };
+
+    let error = pool.publish(&request, &"66".repeat(32), "").await.unwrap_err();
+    assert_eq!(error.1.0.error.unwrap().error, "NODE_RPC_REJECTED");
+    assert_eq!(pool.status().recent_requests[0].outcome, "rejected");
+}
+
+#[test]
+fn test_chain_state_follows_published_blocks() {
+    let mut states = BTreeMap::new();
+    advance_chain_state(&mut states, &published_block(&"0".repeat(64), &"aa".repeat(32), "1000"), 1);
+    advance_chain_state(&mut states, &published_block(&"AA".repeat(32), &"bb".repeat(32), "400"), 2);
+
+    let state = &states[ADDRESS];
+    assert_eq!(state.frontier, "BB".repeat(32));
+    assert_eq!(state.balance, "400");
+    assert_eq!(state.height, Some(2));
+    assert_eq!(state.lagging_frontiers, vec!["0".repeat(64), "AA".repeat(32)]);
+
+    // A block on top of an unknown frontier starts a fresh trail without a height.
+    advance_chain_state(&mut states, &published_block(&"CC".repeat(32), &"dd".repeat(32), "300"), 3);
+    assert_eq!(states[ADDRESS].height, None);
+    assert_eq!(states[ADDRESS].lagging_frontiers, vec!["CC".repeat(32)]);
+}
+
+#[test]
+fn test_chain_state_is_keyed_by_canonical_address() {
+    let mut states = BTreeMap::new();
+    let mut presigned = published_block(&"0".repeat(64), &"aa".repeat(32), "1000");
+    presigned.block.account = ADDRESS.replacen("nano_", "xrb_", 1).to_uppercase();
+    advance_chain_state(&mut states, &presigned, 1);
+
+    let mut keyed = published_block(&"AA".repeat(32), &"bb".repeat(32), "400");
+    keyed.block.account = "ignored".to_string();
+    keyed.account_public_key = nano_address_to_public_key(ADDRESS, true).unwrap();
+    advance_chain_state(&mut states, &keyed, 2);
+
+    assert_eq!(states.keys().collect::<Vec<_>>(), vec![ADDRESS]);
+    assert_eq!(states[ADDRESS].frontier, "BB".repeat(32));
+    assert_eq!(states[ADDRESS].height, Some(2));
+}
+
+#[test]
+fn test_chain_state_reconciliation() {
+    let mut states = BTreeMap::new();
+    advance_chain_state(&mut states, &published_block(&"AA".repeat(32), &"bb".repeat(32), "400"), 1);
+    let cached = states[ADDRESS].clone();
+
+    // Backend has not seen our block yet: keep building on it.
+    let lagging = reconciled_chain_state(Some(&cached), Some(&account_info(&"aa".repeat(32), "1000", Some("1"))), 2).unwrap();
+    assert_eq!(lagging, cached);
+
+    // Backend caught up: its view wins and the trail is cleared.
+    let synced = reconciled_chain_state(Some(&cached), Some(&account_info(&"BB".repeat(32), "400", Some("2"))), 2).unwrap();
+    assert_eq!((synced.height, synced.lagging_frontiers.len()), (Some(2), 0));
+
+    // Chain moved on elsewhere: the backend wins.
+    let moved = reconciled_chain_state(Some(&cached), Some(&account_info(&"EE".repeat(32), "50", None)), 2).unwrap();
+    assert_eq!((moved.frontier.as_str(), moved.balance.as_str(), moved.height), ("EE".repeat(32).as_str(), "50", None));
+
+    // Account unknown to the backend: fall back to the cache, or to an unopened account.
+    assert_eq!(reconciled_chain_state(Some(&cached), None, 2), Some(cached.clone()));
+    assert_eq!(reconciled_chain_state(Some(&cached), None, 1 + 121), None);
+    assert_eq!(reconciled_chain_state(None, None, 2), None);
+
+    // Our block never landed: once the lagging window has passed, the backend wins again.
+    let expired = reconciled_chain_state(Some(&cached), Some(&account_info(&"aa".repeat(32), "1000", Some("1"))), 1 + 121).unwrap();
+    assert_eq!((expired.frontier.as_str(), expired.balance.as_str()), ("AA".repeat(32).as_str(), "1000"));
+
+    // A backend with at least as many blocks as our chain is not lagging, whatever frontier it reports.
+    advance_chain_state(&mut states, &published_block(&"0".repeat(64), &"aa".repeat(32), "1000"), 1);
+    advance_chain_state(&mut states, &published_block(&"AA".repeat(32), &"bb".repeat(32), "400"), 1);
+    let counted = states[ADDRESS].clone();
+    assert_eq!(reconciled_chain_state(Some(&counted), Some(&account_info(&"aa".repeat(32), "1000", Some("1"))), 2), Some(counted.clone()));
+    let diverged = reconciled_chain_state(Some(&counted), Some(&account_info(&"aa".repeat(32), "1000", Some("2"))), 2).unwrap();
+    assert_eq!((diverged.frontier.as_str(), diverged.height), ("AA".repeat(32).as_str(), Some(2)));
+}
+
+#[test]
+fn test_only_account_not_found_means_unopened() {
+    let rejected = |details: &str| NodeRpcError::Rejected(details.to_string()).to_response();
+    assert!(is_account_not_found(&rejected("Account not found")));
+    assert!(!is_account_not_found(&rejected("Bad account number")));
+    assert!(!is_account_not_found(&NodeRpcError::Unreachable.to_response()));
+    assert!(!is_account_not_found(&NodeRpcError::InvalidResponse.to_response()));
+}
@@ -1 +1,10 @@
-pub mod test;
\ No newline at end of file
+pub mod test;
+pub mod nano;
+pub mod wallet;
+pub mod shamir;
+pub mod uri;
+pub mod qr;
+pub mod block;
+pub mod ledger;
+pub mod queue;
+pub mod recovery;
\ No newline at end of file
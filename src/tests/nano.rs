@@ -0,0 +1,104 @@
+#![cfg(test)]
+
+use crate::helpers::nano::{bip39_mnemonic_to_seed, bip44_nano_private_key, nano_account_private_key, nano_message_hash, nano_private_key_to_public_key, 
+    public_key_to_nano_address, sign_hash_with_private_key, validate_nano_address, verify_hash_signature};
+use crate::structs::util::AddressValidationReason;
+use crate::services::wallet::wallet::derive_wallet_data;
+use crate::structs::wallet::WalletSeedType;
+
+const ZERO_SEED: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+const BIP39_MNEMONIC: &str = "edge defense waste choose enrich upon flee junk siren film clown finish luggage leader kid quick brick print evidence swap drill paddle truly occur";
+const BIP39_PASSPHRASE: &str = "some password";
+
+#[test]
+fn test_nano_seed_derivation() {
+    let private_key = nano_account_private_key(ZERO_SEED, 0).unwrap();
+    assert_eq!(hex::encode_upper(&private_key), "9F0E444C69F77A49BD0BE89DB92C38FE713E0963165CCA12FAF5712D7657120F");
+
+    let public_key = hex::encode_upper(nano_private_key_to_public_key(&private_key).unwrap());
+    assert_eq!(public_key, "C008B814A7D269A1FA3C6528B19201A24D797912DB9996FF02A1FF356E45552B");
+    assert_eq!(public_key_to_nano_address(&public_key).unwrap(), "nano_3i1aq1cchnmbn9x5rsbap8b15akfh7wj7pwskuzi7ahz8oq6cobd99d4r3b7");
+}
+
+#[test]
+fn test_bip39_derivation() {
+    let seed = bip39_mnemonic_to_seed(BIP39_MNEMONIC, BIP39_PASSPHRASE).unwrap();
+    assert_eq!(hex::encode(&seed), "0dc285fde768f7ff29b66ce7252d56ed92fe003b605907f7a4f683c3dc8586d34a914d3c71fc099bb38ee4a59e5b081a3497b7a323e90cc68f67b5837690310c");
+
+    let private_key = bip44_nano_private_key(&seed, 0).unwrap();
+    assert_eq!(hex::encode(&private_key), "3be4fc2ef3f3b7374e6fc4fb6e7bb153f8a2998b3b3dab50853eabe128024143");
+
+    let public_key = hex::encode(nano_private_key_to_public_key(&private_key).unwrap());
+    assert_eq!(public_key, "5b65b0e8173ee0802c2c3e6c9080d1a16b06de1176c938a924f58670904e82c4");
+    assert_eq!(public_key_to_nano_address(&public_key).unwrap(), "nano_1pu7p5n3ghq1i1p4rhmek41f5add1uh34xpb94nkbxe8g4a6x1p69emk8y1d");
+}
+
+#[test]
+fn test_derive_wallet_data_per_seed_type() {
+    let nano_wallet = derive_wallet_data(WalletSeedType::Nano, ZERO_SEED, "").unwrap();
+    assert_eq!(nano_wallet.address, "nano_3i1aq1cchnmbn9x5rsbap8b15akfh7wj7pwskuzi7ahz8oq6cobd99d4r3b7");
+
+    let private_key_wallet = derive_wallet_data(WalletSeedType::PrivateKey, nano_wallet.private_key.expose(), "").unwrap();
+    assert_eq!(private_key_wallet.address, nano_wallet.address);
+    assert!(private_key_wallet.wallet_private_seed.expose().is_empty());
+
+    let seed = hex::encode(bip39_mnemonic_to_seed(BIP39_MNEMONIC, BIP39_PASSPHRASE).unwrap());
+    let bip39_wallet = derive_wallet_data(WalletSeedType::Bip39, &seed, "").unwrap();
+    assert_eq!(bip39_wallet.address, "nano_1pu7p5n3ghq1i1p4rhmek41f5add1uh34xpb94nkbxe8g4a6x1p69emk8y1d");
+}
+
+#[test]
+fn test_message_signature_round_trip() {
+    let private_key = nano_account_private_key(ZERO_SEED, 0).unwrap();
+    let public_key = nano_private_key_to_public_key(&private_key).unwrap();
+
+    let hash = nano_message_hash(&public_key, "I own this account").unwrap();
+    let signature = hex::decode(sign_hash_with_private_key(&private_key, &hash).unwrap()).unwrap();
+    assert!(verify_hash_signature(&public_key, &hash, &signature).unwrap());
+
+    let other_hash = nano_message_hash(&public_key, "I own this account!").unwrap();
+    assert!(!verify_hash_signature(&public_key, &other_hash, &signature).unwrap());
+
+    let mut tampered = signature.clone();
+    tampered[0] ^= 1;
+    assert!(!verify_hash_signature(&public_key, &hash, &tampered).unwrap());
+
+    let other_public_key = nano_private_key_to_public_key(&nano_account_private_key(ZERO_SEED, 1).unwrap()).unwrap();
+    assert!(!verify_hash_signature(&other_public_key, &nano_message_hash(&other_public_key, "I own this account").unwrap(), &signature).unwrap());
+}
+
+/// Zero-seed account 0 signing "I own this account". Hash and signature were computed outside this crate with a
+/// separate ed25519-blake2b implementation (RFC 8032 arithmetic, blake2b-512 as the hash) over the dummy block.
+#[test]
+fn test_message_signature_vector() {
+    let private_key = nano_account_private_key(ZERO_SEED, 0).unwrap();
+    let public_key = nano_private_key_to_public_key(&private_key).unwrap();
+    let hash = nano_message_hash(&public_key, "I own this account").unwrap();
+
+    assert_eq!(hex::encode_upper(&hash), "1E978878AB6BF50D3545F2A8077F48A1F783B206F8EEEB3A5D74D66F542D27C8");
+    let signature = sign_hash_with_private_key(&private_key, &hash).unwrap();
+    assert_eq!(
+        signature.to_uppercase(),
+        "03AF21EB15DE8CE0FE6DAD0AD8F57E6D5CB7151D6F80AC3F6B741B0461CA51E37CFF1C0185A53C0A0B3F293848226FF78A99668229482BEC31740375A4F80309"
+    );
+
+    let genesis_public_key = hex::decode("E89208DD038FBB269987689621D52292AE9C35941A7484756ECCED92A65093BA").unwrap();
+    let genesis_hash = hex::decode("991CF190094C00F0B68E2E5F75F6BEE95A2E0BD93CEAA4A6734DB9F19B728948").unwrap();
+    let genesis_signature = hex::decode("9F0C933C8ADE004D808EA1985FA746A7E95BA2A38F867640F53EC8F180BDFE9E2C1268DEAD7C2664F356E37ABA362BC58E46DBA03E523A7B5A19E4B6EB12BB02").unwrap();
+    assert!(verify_hash_signature(&genesis_public_key, &genesis_hash, &genesis_signature).unwrap());
+}
+
+#[test]
+fn test_address_validation_reasons() {
+    let address = "nano_3i1aq1cchnmbn9x5rsbap8b15akfh7wj7pwskuzi7ahz8oq6cobd99d4r3b7";
+    let public_key = "C008B814A7D269A1FA3C6528B19201A24D797912DB9996FF02A1FF356E45552B";
+
+    assert_eq!(validate_nano_address(address).unwrap(), (address.to_string(), public_key.to_string()));
+    assert_eq!(validate_nano_address(&address.replace("nano_", "xrb_")).unwrap().0, address);
+
+    assert_eq!(validate_nano_address(&address[5..]), Err(AddressValidationReason::MissingPrefix));
+    assert_eq!(validate_nano_address(&address[..64]), Err(AddressValidationReason::InvalidLength));
+    assert_eq!(validate_nano_address(&address.replace("3i1", "3l1")), Err(AddressValidationReason::InvalidCharacter));
+    assert_eq!(validate_nano_address(&address.replace("r3b7", "r3b8")), Err(AddressValidationReason::InvalidChecksum));
+}
@@ -0,0 +1,34 @@
+#![cfg(test)]
+
+use crate::helpers::qr::render_qr;
+use crate::structs::qr::QrFormat;
+
+const URI: &str = "nano:nano_3i1aq1cchnmbn9x5rsbap8b15akfh7wj7pwskuzi7ahz8oq6cobd99d4r3b7?amount=1000000000000000000000000000000";
+
+#[test]
+fn test_render_qr_formats() {
+    let svg = String::from_utf8(render_qr(URI, QrFormat::Svg, None).unwrap()).unwrap();
+    assert!(svg.contains("<svg"));
+
+    let png = render_qr(URI, QrFormat::Png, Some(128)).unwrap();
+    assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+
+    let ascii = String::from_utf8(render_qr(URI, QrFormat::Ascii, None).unwrap()).unwrap();
+    let lines: Vec<&str> = ascii.lines().collect();
+    assert!(lines.len() > 21);
+    assert!(lines.iter().all(|line| line.chars().all(|c| c == '#' || c == ' ')));
+}
+
+#[test]
+fn test_render_qr_rejects_bad_input() {
+    assert!(render_qr(URI, QrFormat::Png, Some(16)).is_err());
+    assert!(render_qr(URI, QrFormat::Svg, Some(4096)).is_err());
+    assert!(render_qr(&"x".repeat(8000), QrFormat::Svg, None).is_err());
+}
+
+#[test]
+fn test_qr_format_from_query() {
+    assert_eq!(serde_json::from_str::<QrFormat>("\"png\"").unwrap(), QrFormat::Png);
+    assert_eq!(QrFormat::default(), QrFormat::Svg);
+    assert!(serde_json::from_str::<QrFormat>("\"gif\"").is_err());
+}
@@ -0,0 +1,65 @@
+#![cfg(test)]
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::services::nano::queue::{account_queue, has_account_queue, run_queued};
+
+#[tokio::test]
+async fn test_operations_on_one_account_run_in_order() {
+    let address = "nano_queue_order";
+    let log = Arc::new(Mutex::new(Vec::new()));
+
+    let mut tasks = Vec::new();
+    for index in 0..3 {
+        let log = log.clone();
+        tasks.push(tokio::spawn(async move {
+            let (_, position) = run_queued(address, "send", || async {
+                log.lock().unwrap().push(format!("start {}", index));
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                log.lock().unwrap().push(format!("end {}", index));
+            }).await;
+            position
+        }));
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    let queue = account_queue(address);
+    assert_eq!(queue.depth, 3);
+    assert!(queue.operations[0].running && !queue.operations[1].running);
+
+    let mut positions = Vec::new();
+    for task in tasks {
+        positions.push(task.await.unwrap());
+    }
+
+    assert_eq!(positions, vec![0, 1, 2]);
+    assert_eq!(*log.lock().unwrap(), vec!["start 0", "end 0", "start 1", "end 1", "start 2", "end 2"]);
+    assert_eq!(account_queue(address).depth, 0);
+    assert!(!has_account_queue(address));
+}
+
+#[tokio::test]
+async fn test_abandoned_operation_leaves_the_queue() {
+    let address = "nano_queue_abandon";
+
+    let first = tokio::spawn(run_queued(address, "send", || tokio::time::sleep(Duration::from_millis(50))));
+    tokio::time::sleep(Duration::from_millis(5)).await;
+    let waiting = tokio::spawn(run_queued(address, "change", || async {}));
+    tokio::time::sleep(Duration::from_millis(5)).await;
+    assert_eq!(account_queue(address).depth, 2);
+
+    waiting.abort();
+    let _ = waiting.await;
+    assert_eq!(account_queue(address).depth, 1);
+    assert!(has_account_queue(address));
+
+    first.await.unwrap();
+    let (_, position) = run_queued(address, "receive", || async {}).await;
+    assert_eq!(position, 0);
+
+    let (_, other_position) = run_queued("nano_queue_other", "send", || async {}).await;
+    assert_eq!(other_position, 0);
+    assert!(!has_account_queue(address) && !has_account_queue("nano_queue_other"));
+}
@@ -0,0 +1,180 @@
+#![cfg(test)]
+
+use axum::Json;
+use reqwest::StatusCode;
+use serde_json::{Value, json};
+
+use std::sync::Arc;
+
+use crate::enums::api::error::{block::BlockError, node::NodeRpcError, publish::PublishError, work_server::WorkServerError};
+use crate::services::ledger::{backend::LedgerBackend, chain::{ChainStateStore, TEST_CHAIN_STATES}, pool::LedgerPool};
+use crate::services::nano::block::{TEST_WORK_SERVER_URL, sign_block_offline};
+use crate::services::nano::recovery::{BlockSource, PublishFailure, attempt_trail, publish_with_recovery};
+use crate::services::nano::validation::validate_block;
+use crate::services::wallet::wallet::{create_wallet_account, derive_wallet_data};
+use crate::structs::api::{ApiError, ApiErrorResult, ApiResponse};
+use crate::structs::block::{AccountStateApi, BlockIntent, PublishAttemptApi, SignedBlock};
+use crate::structs::wallet::{WalletAccount, WalletData, WalletSeedType};
+use crate::tests::ledger::spawn_scripted_node;
+
+const ZERO_SEED: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+const REPRESENTATIVE: &str = "nano_3i1aq1cchnmbn9x5rsbap8b15akfh7wj7pwskuzi7ahz8oq6cobd99d4r3b7";
+const SOURCE_HASH: &str = "5E5E5E5E5E5E5E5E5E5E5E5E5E5E5E5E5E5E5E5E5E5E5E5E5E5E5E5E5E5E5E5E";
+/// Frontiers the backend reports after the cached chain lost, with work precomputed at the receive threshold.
+const GAP_BACKEND_FRONTIER: &str = "A1A1A1A1A1A1A1A1A1A1A1A1A1A1A1A1A1A1A1A1A1A1A1A1A1A1A1A1A1A1A1A1";
+const GAP_BACKEND_FRONTIER_WORK: &str = "00000000006827f0";
+const FORK_BACKEND_FRONTIER: &str = "F2F2F2F2F2F2F2F2F2F2F2F2F2F2F2F2F2F2F2F2F2F2F2F2F2F2F2F2F2F2F2F2";
+const FORK_BACKEND_FRONTIER_WORK: &str = "000000000064fcf4";
+/// Frontiers in the chain state cache that the ledger does not build on.
+const PHANTOM_FRONTIER: &str = "C1C1C1C1C1C1C1C1C1C1C1C1C1C1C1C1C1C1C1C1C1C1C1C1C1C1C1C1C1C1C1C1";
+const FORKED_FRONTIER: &str = "C2C2C2C2C2C2C2C2C2C2C2C2C2C2C2C2C2C2C2C2C2C2C2C2C2C2C2C2C2C2C2C2";
+
+/// An error as the ifenpay API returns it, always with HTTP 500.
+fn ifenpay_rpc_error(code: &str, message: &str) -> ApiErrorResult {
+    (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse {
+        success: false,
+        data: None,
+        error: Some(ApiError { error: code.to_string(), message: message.to_string() }),
+    }))
+}
+
+#[test]
+fn test_classify_publish_failures() {
+    let rejected = |details: &str| NodeRpcError::Rejected(details.to_string()).to_response();
+
+    assert_eq!(PublishFailure::classify(&rejected("Fork")), PublishFailure::Fork);
+    assert_eq!(PublishFailure::classify(&rejected("Gap previous block")), PublishFailure::GapPrevious);
+    assert_eq!(PublishFailure::classify(&rejected("Old block")), PublishFailure::OldBlock);
+    assert_eq!(PublishFailure::classify(&rejected("already processing fork")), PublishFailure::Fork);
+    assert_eq!(PublishFailure::classify(&rejected("Block work is less than threshold")), PublishFailure::InsufficientWork);
+    assert_eq!(PublishFailure::classify(&BlockError::InsufficientWork.to_response()), PublishFailure::InsufficientWork);
+    assert_eq!(PublishFailure::classify(&NodeRpcError::Unreachable.to_response()), PublishFailure::Unreachable);
+    assert_eq!(PublishFailure::classify(&WorkServerError::Unreachable("connection refused".to_string()).to_response()), PublishFailure::WorkUnavailable);
+    assert_eq!(PublishFailure::classify(&WorkServerError::Error("not json".to_string()).to_response()), PublishFailure::WorkUnavailable);
+    assert_eq!(PublishFailure::classify(&rejected("Bad signature")), PublishFailure::Rejected);
+    assert_eq!(PublishFailure::classify(&rejected("Gap source block")), PublishFailure::Rejected);
+
+    // The ifenpay API passes node errors through as HTTP 500; they are still the node's answer.
+    let passed_through = |details: &str| ifenpay_rpc_error("RPC_RESPONSE_ERROR", &format!("RPC response indicates an error: {}", details));
+    assert_eq!(PublishFailure::classify(&passed_through("Bad signature")), PublishFailure::Rejected);
+    assert_eq!(PublishFailure::classify(&passed_through("Fork")), PublishFailure::Fork);
+    assert_eq!(PublishFailure::classify(&passed_through("Gap previous block")), PublishFailure::GapPrevious);
+    assert_eq!(PublishFailure::classify(&ifenpay_rpc_error("RPC_REQUEST_ERROR", "Failed to send RPC request: timed out")), PublishFailure::Unreachable);
+}
+
+#[test]
+fn test_attempt_trail() {
+    let attempts = vec![
+        PublishAttemptApi {
+            attempt: 1,
+            hash: "AA".repeat(32),
+            previous: "11".repeat(32),
+            outcome: "fork".to_string(),
+            error: Some("NODE_RPC_REJECTED: Nano node RPC returned an error: Fork".to_string()),
+            recovery: Some("rebuilt_with_new_work".to_string()),
+        },
+        PublishAttemptApi {
+            attempt: 2,
+            hash: "BB".repeat(32),
+            previous: "22".repeat(32),
+            outcome: "published".to_string(),
+            error: None,
+            recovery: None,
+        },
+    ];
+
+    assert_eq!(
+        attempt_trail(&attempts),
+        "1. fork (NODE_RPC_REJECTED: Nano node RPC returned an error: Fork) -> rebuilt_with_new_work; 2. published"
+    );
+
+    let (status, response) = PublishError::Fork(attempt_trail(&attempts[..1])).to_response();
+    let error = response.0.error.unwrap();
+    assert_eq!(status.as_u16(), 409);
+    assert_eq!(error.error, "PUBLISH_FORK");
+    assert!(error.message.ends_with("-> rebuilt_with_new_work"));
+}
+/// A receive of 500 raw on `frontier`, signed like the toolset's first attempt would be. Its work is never checked by the mock node.
+fn first_attempt(wallet_data: &WalletData, account: &WalletAccount, frontier: &str) -> SignedBlock {
+    let state = AccountStateApi {
+        frontier: frontier.to_string(),
+        balance: "1000".to_string(),
+        representative: REPRESENTATIVE.to_string(),
+    };
+    let offline = sign_block_offline(wallet_data, account, &state, BlockIntent::Receive { amount_raw: 500, link: SOURCE_HASH }).unwrap();
+
+    SignedBlock {
+        block: offline.block,
+        hash: offline.hash,
+        account_public_key: account.public_key.clone(),
+        subtype: offline.subtype,
+        work_root: offline.work_root,
+        threshold_hex: String::new(),
+        work_value_hex: String::new(),
+    }
+}
+
+fn mock_reply(request: &Value, gap_address: &str) -> Value {
+    let is = |field: &Value, expected: &str| field.as_str().is_some_and(|value| value.eq_ignore_ascii_case(expected));
+    match request["action"].as_str() {
+        Some("account_info") => {
+            let frontier = if is(&request["account"], gap_address) { GAP_BACKEND_FRONTIER } else { FORK_BACKEND_FRONTIER };
+            json!({ "frontier": frontier, "balance": "1000", "representative": REPRESENTATIVE, "block_count": "3" })
+        }
+        Some("block_info") => json!({ "error": "Block not found" }),
+        Some("process") if is(&request["block"]["previous"], PHANTOM_FRONTIER) => json!({ "error": "Gap previous block" }),
+        Some("process") if is(&request["block"]["previous"], FORKED_FRONTIER) => json!({ "error": "Fork" }),
+        Some("process") => json!({ "hash": "77".repeat(32) }),
+        Some("work_generate") if is(&request["hash"], GAP_BACKEND_FRONTIER) => json!({ "work": GAP_BACKEND_FRONTIER_WORK, "difficulty": "", "multiplier": "" }),
+        Some("work_generate") if is(&request["hash"], FORK_BACKEND_FRONTIER) => json!({ "work": FORK_BACKEND_FRONTIER_WORK, "difficulty": "", "multiplier": "" }),
+        _ => json!({ "error": "Unknown command" }),
+    }
+}
+
+/// A gap on a cached frontier the ledger does not know and a fork against another block are both repaired the
+/// same way: the backend's account state replaces the cache, the block is rebuilt and re-signed on it with new work,
+/// and the retry is published.
+#[tokio::test]
+async fn test_recovery_rebuilds_on_backend_frontier() {
+    let mut wallet_data = derive_wallet_data(WalletSeedType::Nano, ZERO_SEED, "").unwrap();
+    let gap_account = create_wallet_account(&mut wallet_data, None).unwrap();
+    let fork_account = create_wallet_account(&mut wallet_data, None).unwrap();
+
+    let gap_address = gap_account.address.clone();
+    let node_url = spawn_scripted_node(move |request| mock_reply(request, &gap_address)).await;
+    let pool: &'static LedgerPool = Box::leak(Box::new(LedgerPool::new(vec![LedgerBackend::NodeRpc(node_url.clone())])));
+
+    // Chain state goes to a temp dir instead of `data/`, and the mock node answers `work_generate` too.
+    let chain_state_dir = std::env::temp_dir().join(format!("nano-toolset-recovery-{}", std::process::id()));
+    std::fs::create_dir_all(&chain_state_dir).unwrap();
+    let chain_states = Arc::new(ChainStateStore::open(chain_state_dir.join("chain_state.json")));
+
+    let run_cases = async {
+        let cases = [
+            (&gap_account, PHANTOM_FRONTIER, GAP_BACKEND_FRONTIER, "gap_previous"),
+            (&fork_account, FORKED_FRONTIER, FORK_BACKEND_FRONTIER, "fork"),
+        ];
+        for (account, cached_frontier, backend_frontier, failure) in cases {
+            let rejected = first_attempt(&wallet_data, account, cached_frontier);
+            let intent = BlockIntent::Receive { amount_raw: 500, link: SOURCE_HASH };
+            let source = BlockSource::Wallet { wallet_data: &wallet_data, account, intent };
+
+            let (published, attempts) = publish_with_recovery(pool, rejected.clone(), source, "").await.unwrap();
+
+            let trail: Vec<(&str, Option<&str>)> = attempts.iter().map(|attempt| (attempt.outcome.as_str(), attempt.recovery.as_deref())).collect();
+            assert_eq!(trail, vec![(failure, Some("rebuilt_with_new_work")), ("published", None)]);
+            assert_eq!(attempts[0].hash, rejected.hash);
+            assert_eq!(attempts[1].hash, published.hash);
+
+            assert_eq!(published.block.previous, backend_frontier);
+            assert_eq!(published.block.balance, "1500");
+            assert_ne!(published.hash, rejected.hash);
+            assert_eq!(published.work_root.to_uppercase(), backend_frontier);
+            assert!(validate_block(&published.block, "receive", 1000).is_ok());
+        }
+    };
+    TEST_CHAIN_STATES.scope(chain_states, TEST_WORK_SERVER_URL.scope(node_url, run_cases)).await;
+
+    assert!(chain_state_dir.join("chain_state.json").exists());
+    std::fs::remove_dir_all(&chain_state_dir).unwrap();
+}
@@ -0,0 +1,56 @@
+#![cfg(test)]
+
+use crate::helpers::nano::hex_to_bytes;
+use crate::helpers::shamir::{SecretShare, combine_shares, split_secret};
+
+const SEED: &str = "9F0E444C69F77A49BD0BE89DB92C38FE713E0963165CCA12FAF5712D7657120F";
+
+#[test]
+fn test_any_threshold_subset_recovers_secret() {
+    let seed = hex_to_bytes(SEED).unwrap();
+    let shares = split_secret(&seed, 3, 5).unwrap();
+    assert_eq!(shares.len(), 5);
+
+    for subset in [[0, 1, 2], [0, 2, 4], [4, 3, 1], [1, 2, 3]] {
+        let selected: Vec<SecretShare> = subset.iter().map(|&i| shares[i].clone()).collect();
+        assert_eq!(combine_shares(&selected).unwrap().as_slice(), seed.as_slice());
+    }
+
+    assert_eq!(combine_shares(&shares).unwrap().as_slice(), seed.as_slice());
+}
+
+#[test]
+fn test_fewer_than_threshold_is_rejected() {
+    let seed = hex_to_bytes(SEED).unwrap();
+    let shares = split_secret(&seed, 3, 5).unwrap();
+
+    assert!(combine_shares(&shares[..2]).is_err());
+    assert!(combine_shares(&[shares[0].clone(), shares[0].clone(), shares[1].clone()]).is_err());
+    assert!(split_secret(&seed, 1, 5).is_err());
+    assert!(split_secret(&seed, 6, 5).is_err());
+}
+
+#[test]
+fn test_share_encoding_round_trip() {
+    let seed = hex_to_bytes(SEED).unwrap();
+    let shares = split_secret(&seed, 2, 3).unwrap();
+
+    let encoded: Vec<String> = shares.iter().map(SecretShare::encode).collect();
+    assert!(encoded[0].starts_with("2-1-"));
+
+    let parsed: Vec<SecretShare> = encoded.iter().map(|share| SecretShare::parse(share).unwrap()).collect();
+    assert_eq!(parsed, shares);
+    assert!(SecretShare::parse("2-0-ABCD").is_err());
+    assert!(SecretShare::parse("not-a-share").is_err());
+}
+
+#[test]
+fn test_share_debug_redacts_data() {
+    let seed = hex_to_bytes(SEED).unwrap();
+    let shares = split_secret(&seed, 2, 3).unwrap();
+    let debug = format!("{:?}", shares[0]);
+
+    assert!(debug.contains("[REDACTED]"));
+    assert!(!debug.contains(&hex::encode_upper(shares[0].y.as_slice())));
+    assert!(!debug.contains(&format!("{:?}", shares[0].y.as_slice())));
+}
@@ -9,8 +9,8 @@ use crate::structs::credit::{GetCreditsResponse, TopupCreditsSuccessResponse};
 use crate::structs::donate::DonateResponseApi;
 use crate::structs::payment::CreatePaymentRequestApi;
 use crate::services::wallet::wallet::get_wallet_data;
-use crate::structs::wallet::{BalanceResponseApi, SendNanoRequestApi, SendNanoResponseApi};
-use axum::extract::Path;
+use crate::structs::wallet::{BalanceResponseApi, SendNanoRequestApi, SendNanoResponseApi, WalletSelectorApi};
+use axum::extract::{Path, Query};
 use crate::handlers::payment::get_payment_status_api;
 use crate::handlers::credits::{get_credits_api, topup_credits_api};
 use crate::handlers::wallet::{get_balance_api, send_nano_api};
@@ -37,7 +37,7 @@ async fn test_api_calls() -> Result<(), ApiErrorResult> {
 
 #[cfg(test)]
 async fn get_balance() -> Result<BalanceResponseApi, ApiErrorResult> {
-    let get_balance: Result<ApiSuccessResult<BalanceResponseApi>, ApiErrorResult> = get_balance_api().await;
+    let get_balance: Result<ApiSuccessResult<BalanceResponseApi>, ApiErrorResult> = get_balance_api(Query(WalletSelectorApi::default())).await;
     let get_balance = get_balance?;
     let balance_data = get_balance.0.data.ok_or_else(|| ApiErrorType::ParseError.to_response())?;
 
@@ -52,6 +52,8 @@ async fn send_nano(address: Option<String>, amount: Option<String>) -> Result<Se
     let send_nano_payload = Json(SendNanoRequestApi {
         recipient_address: address.unwrap_or_else(|| balance.account.clone()), // Send to self for testing if no address is provided
         amount: amount.unwrap_or_else(|| "0.000000001".to_string()), // Send the entire balance for testing if no amount is provided
+        wallet: None,
+        account: None,
     });
 
     let send_result: Result<ApiSuccessResult<SendNanoResponseApi>, ApiErrorResult> = send_nano_api(send_nano_payload).await;
@@ -69,7 +71,7 @@ async fn send_nano(address: Option<String>, amount: Option<String>) -> Result<Se
 
 #[cfg(test)]
 async fn donate() -> Result<DonateResponseApi, ApiErrorResult> {
-    let donate_result = donate_api(Path("0.00001".to_string())).await;
+    let donate_result = donate_api(Path("0.00001".to_string()), Query(WalletSelectorApi::default())).await;
     let donate_result = donate_result?;
     let donate_data = donate_result.0.data.ok_or_else(|| ApiErrorType::ParseError.to_response())?;
 
@@ -80,7 +82,7 @@ async fn donate() -> Result<DonateResponseApi, ApiErrorResult> {
 
 #[cfg(test)]
 async fn get_credits() -> Result<GetCreditsResponse, ApiErrorResult> {
-    let get_credits = get_credits_api().await;
+    let get_credits = get_credits_api(Query(WalletSelectorApi::default())).await;
     let get_credits = get_credits?;
     let credits_data = get_credits.0.data.ok_or_else(|| ApiErrorType::ParseError.to_response())?;
 
@@ -92,7 +94,7 @@ async fn get_credits() -> Result<GetCreditsResponse, ApiErrorResult> {
 #[cfg(test)]
 #[allow(dead_code)]
 async fn topup_credits() -> Result<TopupCreditsSuccessResponse, ApiErrorResult> {
-    let topup_result = topup_credits_api(Path(10)).await;
+    let topup_result = topup_credits_api(Path(10), Query(WalletSelectorApi::default())).await;
     let topup_result = topup_result?;
     let topup_data = topup_result.0.data.ok_or_else(|| ApiErrorType::ParseError.to_response())?;
 
@@ -103,12 +105,13 @@ async fn topup_credits() -> Result<TopupCreditsSuccessResponse, ApiErrorResult>
 
 #[cfg(test)]
 async fn process_payment() -> Result<(), ApiErrorResult> {
-    let wallet_data = get_wallet_data();
+    let wallet_data = get_wallet_data(None)?;
 
     let create_payment_payload = CreatePaymentRequestApi {
         receive_address: wallet_data.address.clone(),
         amount: "0.00001".to_string(),
         redirect_url: Some("https://example.com/redirect".to_string()),
+        wallet: None,
     };
 
     let create_payment = create_payment_request_api(Json(create_payment_payload)).await;
@@ -120,6 +123,8 @@ async fn process_payment() -> Result<(), ApiErrorResult> {
     let send_nano_payload = Json(SendNanoRequestApi {
         recipient_address: create_payment_data.receive_address.clone(), // Send to self for testing
         amount: create_payment_data.amount.clone(), // Send the entire balance for testing
+        wallet: None,
+        account: None,
     });
     println!("payload: {:?}", send_nano_payload);
 
@@ -129,7 +134,7 @@ async fn process_payment() -> Result<(), ApiErrorResult> {
     
     sleep(std::time::Duration::from_secs(5)).await;
 
-    let payment_status = get_payment_status_api(Path(create_payment_data.transaction_id.clone())).await;
+    let payment_status = get_payment_status_api(Path(create_payment_data.transaction_id.clone()), Query(WalletSelectorApi::default())).await;
     println!("GET_PAYMENT_STATUS RESULT: {:?}", payment_status.is_ok());
 
     Ok(())
@@ -0,0 +1,46 @@
+#![cfg(test)]
+
+use crate::helpers::nano::raw_to_nano;
+use crate::helpers::uri::{build_nano_uri, parse_nano_uri};
+
+const ADDRESS: &str = "nano_3i1aq1cchnmbn9x5rsbap8b15akfh7wj7pwskuzi7ahz8oq6cobd99d4r3b7";
+
+#[test]
+fn test_raw_to_nano() {
+    assert_eq!(raw_to_nano(0), "0");
+    assert_eq!(raw_to_nano(1_000_000_000_000_000_000_000_000_000_000), "1");
+    assert_eq!(raw_to_nano(1_000_000_000_000_000_000_000_000_000), "0.001");
+    assert_eq!(raw_to_nano(1_500_000_000_000_000_000_000_000_000_001), "1.500000000000000000000000000001");
+}
+
+#[test]
+fn test_parse_nano_uri() {
+    let uri = parse_nano_uri(&format!("nano:{}?amount=1000000000000000000000000000000&label=Coffee%20shop&message=Order+42&foo=bar", ADDRESS.replace("nano_", "xrb_"))).unwrap();
+
+    assert_eq!(uri.address, ADDRESS);
+    assert_eq!(uri.amount_raw.as_deref(), Some("1000000000000000000000000000000"));
+    assert_eq!(uri.amount.as_deref(), Some("1"));
+    assert_eq!(uri.label.as_deref(), Some("Coffee shop"));
+    assert_eq!(uri.message.as_deref(), Some("Order+42"));
+
+    let bare = parse_nano_uri(&format!("NANO:{}", ADDRESS)).unwrap();
+    assert_eq!(bare.amount_raw, None);
+
+    assert!(parse_nano_uri(ADDRESS).is_err());
+    assert!(parse_nano_uri(&format!("nano:{}?amount=1.5", ADDRESS)).is_err());
+    assert!(parse_nano_uri(&format!("nano:{}?label=%E2", ADDRESS)).is_err());
+    assert!(parse_nano_uri("nano:nano_1invalid").is_err());
+}
+
+#[test]
+fn test_build_nano_uri_round_trip() {
+    assert_eq!(build_nano_uri(ADDRESS, None, None, None), format!("nano:{}", ADDRESS));
+
+    let uri = build_nano_uri(ADDRESS, Some(42), Some("Café & co"), Some("order=7"));
+    assert_eq!(uri, format!("nano:{}?amount=42&label=Caf%C3%A9%20%26%20co&message=order%3D7", ADDRESS));
+
+    let parsed = parse_nano_uri(&uri).unwrap();
+    assert_eq!(parsed.amount_raw.as_deref(), Some("42"));
+    assert_eq!(parsed.label.as_deref(), Some("Café & co"));
+    assert_eq!(parsed.message.as_deref(), Some("order=7"));
+}
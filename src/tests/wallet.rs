@@ -0,0 +1,135 @@
+#![cfg(test)]
+
+use aes_gcm::{Aes256Gcm, KeyInit, Nonce};
+use aes_gcm::aead::Aead;
+use argon2::Argon2;
+
+use crate::services::wallet::backup::export_wallet;
+use crate::services::wallet::format::{WalletFileHeader, is_current_wallet_format};
+use crate::services::wallet::registry::reserve_wallet_name;
+use crate::services::wallet::session::{lock_wallet, store_unlocked_wallet, unlocked_wallet, wallet_session_status};
+use crate::services::wallet::wallet::{add_watch_only_account, decrypt_wallet_data, derive_wallet_data, encrypt_wallet_data, remove_watch_only_account, 
+    resolve_monitored_account, resolve_wallet_account};
+use crate::structs::wallet::{WalletData, WalletSeedType};
+
+const ZERO_SEED: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+const PASSWORD: &str = "correct horse battery staple";
+
+fn test_wallet() -> WalletData {
+    derive_wallet_data(WalletSeedType::Nano, ZERO_SEED, "test-api-key").unwrap()
+}
+
+/// Reproduces the headerless `salt || nonce || ciphertext` layout written before format versioning.
+fn encrypt_legacy(wallet_data: &WalletData, password: &str) -> Vec<u8> {
+    let salt = [7u8; 16];
+    let nonce = [9u8; 12];
+    let mut key = [0u8; 32];
+    Argon2::default().hash_password_into(password.as_bytes(), &salt, &mut key).unwrap();
+
+    let cipher = Aes256Gcm::new_from_slice(&key).unwrap();
+    let ciphertext = cipher.encrypt(Nonce::from_slice(&nonce), serde_json::to_vec(wallet_data).unwrap().as_ref()).unwrap();
+
+    [salt.to_vec(), nonce.to_vec(), ciphertext].concat()
+}
+
+#[test]
+fn test_wallet_file_round_trip_has_header() {
+    let encrypted = encrypt_wallet_data(&test_wallet(), PASSWORD).unwrap();
+
+    assert_eq!(WalletFileHeader::parse(&encrypted), Some(WalletFileHeader::current()));
+    assert!(is_current_wallet_format(&encrypted));
+
+    let decrypted = decrypt_wallet_data(&encrypted, PASSWORD).unwrap();
+    assert_eq!(decrypted.address, test_wallet().address);
+    assert!(decrypt_wallet_data(&encrypted, "wrong password").is_err());
+}
+
+#[test]
+fn test_legacy_wallet_file_is_readable() {
+    let legacy = encrypt_legacy(&test_wallet(), PASSWORD);
+
+    assert!(WalletFileHeader::parse(&legacy).is_none());
+    assert!(!is_current_wallet_format(&legacy));
+    assert_eq!(decrypt_wallet_data(&legacy, PASSWORD).unwrap().api_key, "test-api-key");
+}
+
+#[test]
+fn test_wallet_header_is_authenticated() {
+    let mut encrypted = encrypt_wallet_data(&test_wallet(), PASSWORD).unwrap();
+    encrypted[18] = 2;
+    assert!(decrypt_wallet_data(&encrypted, PASSWORD).is_err());
+
+    let mut encrypted = encrypt_wallet_data(&test_wallet(), PASSWORD).unwrap();
+    encrypted[10] ^= 1;
+    assert!(decrypt_wallet_data(&encrypted, PASSWORD).is_err());
+}
+
+#[test]
+fn test_wallet_debug_redacts_secrets() {
+    let wallet_data = test_wallet();
+    let debug = format!("{:?}", wallet_data);
+
+    assert!(debug.contains("[REDACTED]"));
+    assert!(!debug.contains(wallet_data.private_key.expose()));
+    assert!(!debug.contains(ZERO_SEED));
+}
+
+#[test]
+fn test_locked_wallet_is_refused() {
+    let mut wallet_data = test_wallet();
+    wallet_data.name = "session-test".to_string();
+    store_unlocked_wallet(wallet_data);
+
+    assert!(wallet_session_status("session-test").unlocked);
+    assert_eq!(unlocked_wallet("session-test").unwrap().address, test_wallet().address);
+
+    assert!(!lock_wallet("session-test").unlocked);
+    let (status, _) = unlocked_wallet("session-test").unwrap_err();
+    assert_eq!(status, reqwest::StatusCode::LOCKED);
+}
+
+#[test]
+fn test_watch_only_accounts_cannot_sign() {
+    let watched_address = "nano_1pu7p5n3ghq1i1p4rhmek41f5add1uh34xpb94nkbxe8g4a6x1p69emk8y1d";
+    let mut wallet_data = test_wallet();
+
+    add_watch_only_account(&mut wallet_data, watched_address, Some("partner".to_string())).unwrap();
+    assert!(add_watch_only_account(&mut wallet_data, watched_address, None).is_err());
+    assert!(add_watch_only_account(&mut wallet_data, &test_wallet().address, None).is_err());
+
+    let monitored = resolve_monitored_account(&wallet_data, Some("partner")).unwrap();
+    assert!(monitored.is_watch_only());
+    assert_eq!(monitored.address(), watched_address);
+    assert!(!resolve_monitored_account(&wallet_data, None).unwrap().is_watch_only());
+
+    let (status, _) = resolve_wallet_account(&wallet_data, Some(watched_address)).unwrap_err();
+    assert_eq!(status, reqwest::StatusCode::FORBIDDEN);
+
+    remove_watch_only_account(&mut wallet_data, "partner").unwrap();
+    assert!(resolve_monitored_account(&wallet_data, Some("partner")).is_err());
+}
+
+#[test]
+fn test_export_requires_wallet_password() {
+    let mut wallet_data = test_wallet();
+    wallet_data.name = "export-test".to_string();
+    store_unlocked_wallet(wallet_data);
+
+    let (status, response) = export_wallet("export-test", "not the wallet password", Some(PASSWORD)).unwrap_err();
+    assert_eq!(status, reqwest::StatusCode::UNAUTHORIZED);
+    assert_eq!(response.0.error.unwrap().error, "WRONG_PASSWORD");
+
+    lock_wallet("export-test");
+}
+
+#[test]
+fn test_wallet_name_is_reserved_until_stored() {
+    let reservation = reserve_wallet_name("reservation-test").unwrap();
+    let (status, response) = reserve_wallet_name("reservation-test").err().unwrap();
+    assert_eq!(status, reqwest::StatusCode::CONFLICT);
+    assert_eq!(response.0.error.unwrap().error, "WALLET_ALREADY_EXISTS");
+
+    drop(reservation);
+    assert!(reserve_wallet_name("reservation-test").is_ok());
+    assert!(reserve_wallet_name("not a name").is_err());
+}